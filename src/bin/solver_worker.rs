@@ -0,0 +1,5 @@
+use projekttage_yew::solver::worker::registrieren;
+
+fn main() {
+    registrieren();
+}
@@ -0,0 +1,63 @@
+use gloo::dialogs::{confirm, prompt};
+use web_sys::HtmlInputElement;
+use yew::{Callback, Event, Html, MouseEvent, Properties, TargetCast, function_component, html};
+
+#[derive(Properties, PartialEq)]
+pub struct ArbeitsbereichAuswahlProps {
+    // None, solange der Arbeitsbereich noch geladen wird
+    pub aktiv: Option<String>,
+    pub arbeitsbereiche: Vec<String>,
+    pub onwechseln: Callback<String>,
+    pub onneu: Callback<String>,
+    pub onloeschen: Callback<String>,
+}
+
+#[function_component(ArbeitsbereichAuswahl)]
+pub fn arbeitsbereich_auswahl(props: &ArbeitsbereichAuswahlProps) -> Html {
+    let onchange = props
+        .onwechseln
+        .reform(|event: Event| event.target_unchecked_into::<HtmlInputElement>().value());
+
+    let onneu = {
+        let onneu = props.onneu.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(name) = prompt("Name des neuen Arbeitsbereichs", None)
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+            {
+                onneu.emit(name);
+            }
+        })
+    };
+
+    let onloeschen = {
+        let onloeschen = props.onloeschen.clone();
+        let aktiv = props.aktiv.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(name) = &aktiv
+                && confirm(&format!(
+                    "Arbeitsbereich \"{name}\" mit allen Projekten, Schülern und Einteilungen löschen?"
+                ))
+            {
+                onloeschen.emit(name.clone());
+            }
+        })
+    };
+
+    let laedt = props.aktiv.is_none();
+
+    html! {
+        <div class="arbeitsbereich_auswahl">
+            <select { onchange } disabled={ laedt }>
+                if laedt {
+                    <option selected=true>{ "Wird geladen…" }</option>
+                }
+                { for props.arbeitsbereiche.iter().map(|name| html! {
+                    <option value={ name.clone() } selected={ props.aktiv.as_ref() == Some(name) }>{ name.clone() }</option>
+                }) }
+            </select>
+            <button onclick={ onneu } disabled={ laedt }>{ "Neu" }</button>
+            <button onclick={ onloeschen } disabled={ laedt }>{ "Löschen" }</button>
+        </div>
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use yew::{Callback, Event, Html, MouseEvent, Properties, classes, function_component, html};
+
+use crate::{
+    import::vorschau::Aenderung,
+    types::{ProjektId, SaveFileProjekt},
+};
+
+#[derive(Properties, PartialEq)]
+pub struct ImportVorschauProps {
+    pub aenderungen: Vec<Aenderung>,
+    pub angenommen: Vec<bool>,
+    // Projekte nach dem Import, für Namen und unbekannte Wünsche
+    pub projekte: BTreeMap<ProjektId, SaveFileProjekt>,
+    pub onumschalten: Callback<usize>,
+    pub onalle: Callback<bool>,
+    pub onuebernehmen: Callback<()>,
+    pub onverwerfen: Callback<()>,
+}
+
+#[function_component(ImportVorschau)]
+pub fn import_vorschau(props: &ImportVorschauProps) -> Html {
+    let anzahl_angenommen = props.angenommen.iter().filter(|&&a| a).count();
+    let unbekannt = props
+        .aenderungen
+        .iter()
+        .filter(|aenderung| !aenderung.unbekannte_wuensche(&props.projekte).is_empty())
+        .count();
+
+    let alle = props.onalle.reform(|_: MouseEvent| true);
+    let keine = props.onalle.reform(|_: MouseEvent| false);
+    let onuebernehmen = props.onuebernehmen.reform(|_: MouseEvent| ());
+    let onverwerfen = props.onverwerfen.reform(|_: MouseEvent| ());
+
+    html! {
+        <div class="import_vorschau">
+            <h2>{ format!("{} Änderungen aus dem Import", props.aenderungen.len()) }</h2>
+            if unbekannt > 0 {
+                <p class="warnung">{ format!("{unbekannt} Schüler wünschen sich Projekte, die es nicht gibt") }</p>
+            }
+            <button onclick={ alle }>{ "Alle annehmen" }</button>
+            <button onclick={ keine }>{ "Alle ablehnen" }</button>
+            <ul>
+                { for props.aenderungen.iter().zip(props.angenommen.iter()).enumerate().map(|(idx, (aenderung, &angenommen))| {
+                    let onchange = props.onumschalten.reform(move |_: Event| idx);
+                    let unbekannte_wuensche = aenderung.unbekannte_wuensche(&props.projekte);
+
+                    html! {
+                        <li class={ classes!((!angenommen).then_some("verworfen")) }>
+                            <label>
+                                <input type="checkbox" checked={ angenommen } { onchange } />
+                                { aenderung.beschreibung(&props.projekte) }
+                            </label>
+                            if !unbekannte_wuensche.is_empty() {
+                                <span class="warnung">
+                                    { format!(" Unbekannte Projekte: {}", unbekannte_wuensche.iter().map(|p_id| p_id.to_string()).collect::<Vec<String>>().join(", ")) }
+                                </span>
+                            }
+                        </li>
+                    }
+                }) }
+            </ul>
+            <button onclick={ onuebernehmen }>{ format!("{anzahl_angenommen} Änderungen übernehmen") }</button>
+            <button onclick={ onverwerfen }>{ "Import verwerfen" }</button>
+        </div>
+    }
+}
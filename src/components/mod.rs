@@ -1,3 +1,15 @@
+mod arbeitsbereich_auswahl;
+mod import_vorschau;
+mod snapshot_liste;
+mod spalten_auswahl;
+mod statistik;
 mod tabelle;
+mod verlauf_auswahl;
 
+pub use arbeitsbereich_auswahl::ArbeitsbereichAuswahl;
+pub use import_vorschau::ImportVorschau;
+pub use snapshot_liste::SnapshotListe;
+pub use spalten_auswahl::SpaltenAuswahl;
+pub use statistik::Statistik;
 pub use tabelle::Tabelle;
+pub use verlauf_auswahl::VerlaufAuswahl;
@@ -0,0 +1,185 @@
+use gloo::dialogs::{confirm, prompt};
+use web_sys::HtmlInputElement;
+use yew::{
+    Callback, Event, Html, MouseEvent, Properties, TargetCast, function_component, html, use_state,
+};
+
+use crate::types::{Snapshot, SolveReportZeile};
+
+#[derive(Properties, PartialEq)]
+pub struct SnapshotListeProps {
+    pub snapshots: Vec<Snapshot>,
+    // Die aktuelle Einteilung, damit sie mit einem Snapshot verglichen werden kann
+    pub aktuell: Snapshot,
+    pub onsichern: Callback<String>,
+    pub onwiederherstellen: Callback<usize>,
+    pub onumbenennen: Callback<(usize, String)>,
+    pub onloeschen: Callback<usize>,
+}
+
+#[function_component(SnapshotListe)]
+pub fn snapshot_liste(props: &SnapshotListeProps) -> Html {
+    // None steht für die aktuelle Einteilung
+    let vergleich = use_state(|| (None::<usize>, props.snapshots.len().checked_sub(1)));
+
+    // Gelöschte Snapshots fallen auf die aktuelle Einteilung zurück
+    let (links, rechts) = *vergleich;
+    let links = links.filter(|&idx| idx < props.snapshots.len());
+    let rechts = rechts.filter(|&idx| idx < props.snapshots.len());
+
+    let snapshot = |idx: Option<usize>| match idx {
+        Some(idx) => &props.snapshots[idx],
+        None => &props.aktuell,
+    };
+
+    let onsichern = {
+        let onsichern = props.onsichern.clone();
+        let name = format!("Einteilung {}", props.snapshots.len() + 1);
+        Callback::from(move |_: MouseEvent| {
+            if let Some(name) = prompt("Name des Snapshots", Some(&name))
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+            {
+                onsichern.emit(name);
+            }
+        })
+    };
+
+    let vergleich_auswahl = |seite: usize| {
+        let vergleich = vergleich.clone();
+        let onchange = Callback::from(move |event: Event| {
+            let wert =
+                str::parse::<usize>(&event.target_unchecked_into::<HtmlInputElement>().value())
+                    .ok();
+            let (links, rechts) = *vergleich;
+            vergleich.set(if seite == 0 {
+                (wert, rechts)
+            } else {
+                (links, wert)
+            });
+        });
+        let aktiv = if seite == 0 { links } else { rechts };
+
+        html! {
+            <select { onchange }>
+                <option value="aktuell" selected={ aktiv.is_none() }>{ "Aktuelle Einteilung" }</option>
+                { for props.snapshots.iter().enumerate().map(|(idx, snapshot)| html! {
+                    <option value={ idx.to_string() } selected={ aktiv == Some(idx) }>{ format!("{} ({})", snapshot.name, snapshot.zeitpunkt) }</option>
+                }) }
+            </select>
+        }
+    };
+
+    html! {
+        <details class="snapshots">
+            <summary>{ format!("{} gespeicherte Einteilungen", props.snapshots.len()) }</summary>
+            <button onclick={ onsichern }>{ "Aktuelle Einteilung sichern" }</button>
+            <ul>
+                { for props.snapshots.iter().enumerate().rev().map(|(idx, snapshot)| {
+                    let onwiederherstellen = {
+                        let onwiederherstellen = props.onwiederherstellen.clone();
+                        let name = snapshot.name.clone();
+                        Callback::from(move |_: MouseEvent| {
+                            if confirm(&format!("Die aktuelle Einteilung durch \"{name}\" ersetzen?")) {
+                                onwiederherstellen.emit(idx);
+                            }
+                        })
+                    };
+                    let onumbenennen = {
+                        let onumbenennen = props.onumbenennen.clone();
+                        let name = snapshot.name.clone();
+                        Callback::from(move |_: MouseEvent| {
+                            if let Some(name) = prompt("Neuer Name", Some(&name))
+                                .map(|name| name.trim().to_string())
+                                .filter(|name| !name.is_empty())
+                            {
+                                onumbenennen.emit((idx, name));
+                            }
+                        })
+                    };
+                    let onloeschen = {
+                        let onloeschen = props.onloeschen.clone();
+                        let name = snapshot.name.clone();
+                        Callback::from(move |_: MouseEvent| {
+                            if confirm(&format!("\"{name}\" löschen?")) {
+                                onloeschen.emit(idx);
+                            }
+                        })
+                    };
+
+                    html! {
+                        <li>
+                            <span>{ format!("{} ({})", snapshot.name, snapshot.zeitpunkt) }</span>
+                            <button onclick={ onwiederherstellen }>{ "Wiederherstellen" }</button>
+                            <button onclick={ onumbenennen }>{ "Umbenennen" }</button>
+                            <button onclick={ onloeschen }>{ "Löschen" }</button>
+                        </li>
+                    }
+                }) }
+            </ul>
+            <h3>{ "Vergleich" }</h3>
+            <table class="vergleich">
+                <thead>
+                    <tr>
+                        <th></th>
+                        <th>{ vergleich_auswahl(0) }</th>
+                        <th>{ vergleich_auswahl(1) }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { vergleich_zeilen(snapshot(links), snapshot(rechts)) }
+                    <tr>
+                        <td>{ "Anders eingeteilte Schüler" }</td>
+                        <td colspan="2">{ snapshot(links).unterschiede(snapshot(rechts)) }</td>
+                    </tr>
+                </tbody>
+            </table>
+        </details>
+    }
+}
+
+fn vergleich_zeilen(links: &Snapshot, rechts: &Snapshot) -> Html {
+    let zeile = |name: &str, wert: &dyn Fn(&Snapshot) -> String| {
+        html! {
+            <tr>
+                <td>{ name }</td>
+                <td>{ wert(links) }</td>
+                <td>{ wert(rechts) }</td>
+            </tr>
+        }
+    };
+
+    // Anteil an allen eingeteilten Schülern
+    let anteil = |snapshot: &Snapshot, anzahl: &dyn Fn(&SolveReportZeile) -> usize| {
+        snapshot
+            .report
+            .as_ref()
+            .map(|report| {
+                let gesamt = &report.gesamt;
+                if gesamt.schueler == 0 {
+                    anzahl(gesamt).to_string()
+                } else {
+                    format!(
+                        "{} ({:.0} %)",
+                        anzahl(gesamt),
+                        anzahl(gesamt) as f64 * 100.0 / gesamt.schueler as f64
+                    )
+                }
+            })
+            .unwrap_or("---".to_string())
+    };
+
+    html! {
+        <>
+            { zeile("Zeitpunkt", &|s| s.zeitpunkt.clone()) }
+            { zeile("Eingeteilte Schüler", &|s| s.report.as_ref().map(|r| r.gesamt.schueler.to_string()).unwrap_or("---".to_string())) }
+            { for (0..5).map(|rang| zeile(&format!("{}. Wunsch", rang + 1), &|s| anteil(s, &|z| z.wish_hist[rang]))) }
+            { zeile("Nicht gewünscht", &|s| anteil(s, &|z| z.not_wished)) }
+            { zeile("Mit Partner", &|s| s.report.as_ref().map(|r| format!("{} von {}", r.gesamt.partner_erhalten, r.gesamt.partner_gewuenscht)).unwrap_or("---".to_string())) }
+            { zeile("Abgesagte Projekte", &|s| s.abgesagt.len().to_string()) }
+            { zeile("Gewichtungen", &|s| s.solver_settings.weights.iter().map(|w| w.to_string()).collect::<Vec<String>>().join(" / ")) }
+            { zeile("Bonus für Wunschpartner", &|s| s.solver_settings.partner_weight.to_string()) }
+            { zeile("Weiche Teilnehmergrenzen", &|s| if s.solver_settings.soft_constraints { "ja".to_string() } else { "nein".to_string() }) }
+        </>
+    }
+}
@@ -0,0 +1,95 @@
+use web_sys::HtmlInputElement;
+use yew::{
+    Callback, Html, MouseEvent, Properties, TargetCast, function_component, html, html::onchange,
+};
+
+use crate::import::{ImportFeld, ImportTabelle};
+
+#[derive(Properties, PartialEq)]
+pub struct SpaltenAuswahlProps {
+    pub titel: String,
+    pub felder: &'static [ImportFeld],
+    pub tabelle: ImportTabelle,
+    pub zuordnung: Vec<Option<usize>>,
+    // Spalten, die als Merkmale übernommen werden, None wenn es keine Merkmale gibt
+    #[prop_or_default]
+    pub merkmale: Option<Vec<usize>>,
+    pub onchange: Callback<(usize, Option<usize>)>,
+    #[prop_or_default]
+    pub onmerkmal: Callback<usize>,
+    pub onimport: Callback<()>,
+    pub onabbrechen: Callback<()>,
+}
+
+#[function_component(SpaltenAuswahl)]
+pub fn spalten_auswahl(props: &SpaltenAuswahlProps) -> Html {
+    let vollstaendig = props
+        .felder
+        .iter()
+        .zip(props.zuordnung.iter())
+        .all(|(feld, spalte)| !feld.pflicht || spalte.is_some());
+
+    let onimport = props.onimport.reform(|_: MouseEvent| ());
+    let onabbrechen = props.onabbrechen.reform(|_: MouseEvent| ());
+
+    html! {
+        <div class="spalten_auswahl">
+            <h2>{ props.titel.clone() }</h2>
+            <div class="felder">
+                { for props.felder.iter().enumerate().map(|(feld_idx, feld)| {
+                    let onchange = props.onchange.reform(move |event: onchange::Event| {
+                        let select = event.target_unchecked_into::<HtmlInputElement>();
+                        (feld_idx, str::parse::<usize>(&select.value()).ok())
+                    });
+                    let ausgewaehlt = props.zuordnung.get(feld_idx).copied().flatten();
+
+                    html! {
+                        <label>
+                            { if feld.pflicht { format!("{} *", feld.name) } else { feld.name.to_string() } }
+                            <select { onchange }>
+                                <option value="" selected={ ausgewaehlt.is_none() }>{ "Nicht vorhanden" }</option>
+                                { for props.tabelle.kopf.iter().enumerate().map(|(spalte, kopf)| html! {
+                                    <option value={ spalte.to_string() } selected={ ausgewaehlt == Some(spalte) }>{ kopf.clone() }</option>
+                                }) }
+                            </select>
+                        </label>
+                    }
+                }) }
+            </div>
+            if let Some(merkmale) = &props.merkmale {
+                <div class="merkmale">
+                    <p>{ "Weitere Spalten als Merkmale übernehmen (z.B. Geschlecht)" }</p>
+                    { for props.tabelle.kopf.iter().enumerate()
+                        .filter(|(spalte, _)| !props.zuordnung.contains(&Some(*spalte)))
+                        .map(|(spalte, kopf)| {
+                            let onchange = props.onmerkmal.reform(move |_: onchange::Event| spalte);
+
+                            html! {
+                                <label>
+                                    <input type="checkbox" checked={ merkmale.contains(&spalte) } { onchange } />
+                                    { kopf.clone() }
+                                </label>
+                            }
+                        }) }
+                </div>
+            }
+            <table class="vorschau">
+                <thead>
+                    <tr>
+                        { for props.tabelle.kopf.iter().map(|kopf| html! (<th>{ kopf.clone() }</th>)) }
+                    </tr>
+                </thead>
+                <tbody>
+                    { for props.tabelle.zeilen.iter().take(5).map(|zeile| html! {
+                        <tr>
+                            { for zeile.iter().map(|zelle| html! (<td>{ zelle.clone() }</td>)) }
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+            <p>{ format!("{} Zeilen", props.tabelle.zeilen.len()) }</p>
+            <button onclick={ onimport } disabled={ !vollstaendig }>{ "Importieren" }</button>
+            <button onclick={ onabbrechen }>{ "Abbrechen" }</button>
+        </div>
+    }
+}
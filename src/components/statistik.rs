@@ -0,0 +1,60 @@
+use yew::{Html, Properties, function_component, html};
+
+use crate::types::{SolveReport, SolveReportZeile};
+
+#[derive(Properties, PartialEq)]
+pub struct StatistikProps {
+    pub report: SolveReport,
+}
+
+#[function_component(Statistik)]
+pub fn statistik(props: &StatistikProps) -> Html {
+    let report = &props.report;
+
+    html! {
+        <div class="statistik">
+            <h2>{ "Statistik der Einteilung" }</h2>
+            <table>
+                <thead>
+                    <tr>
+                        <th>{ "Stufe" }</th>
+                        <th>{ "Schüler" }</th>
+                        { for (1..=5).map(|rang| html! (<th>{ format!("{rang}. Wunsch") }</th>)) }
+                        <th>{ "Nicht gewünscht" }</th>
+                        <th>{ "Ohne Wünsche" }</th>
+                        <th>{ "Mit Partner" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for report.stufen.iter().map(|(stufe, zeile)| statistik_zeile(&stufe.to_string(), zeile)) }
+                    { statistik_zeile("Gesamt", &report.gesamt) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn statistik_zeile(name: &str, zeile: &SolveReportZeile) -> Html {
+    // Anteil an allen eingeteilten Schülern der Zeile
+    let anteil = |anzahl: usize| {
+        if zeile.schueler == 0 {
+            format!("{anzahl}")
+        } else {
+            format!(
+                "{anzahl} ({:.0} %)",
+                anzahl as f64 * 100.0 / zeile.schueler as f64
+            )
+        }
+    };
+
+    html! {
+        <tr class={ if name == "Gesamt" { "gesamt" } else { "" } }>
+            <td>{ name }</td>
+            <td>{ zeile.schueler }</td>
+            { for zeile.wish_hist.iter().map(|&anzahl| html! (<td>{ anteil(anzahl) }</td>)) }
+            <td>{ anteil(zeile.not_wished) }</td>
+            <td>{ zeile.ohne_wuensche }</td>
+            <td>{ format!("{} von {}", zeile.partner_erhalten, zeile.partner_gewuenscht) }</td>
+        </tr>
+    }
+}
@@ -0,0 +1,64 @@
+use yew::{Callback, Html, MouseEvent, function_component, html, use_context};
+
+use crate::DataContext;
+
+#[function_component(VerlaufAuswahl)]
+pub fn verlauf_auswahl() -> Html {
+    let Some(data) = use_context::<DataContext>() else {
+        return html!(<></>);
+    };
+
+    let letzte_aenderung = data
+        .rueckgaengig_liste()
+        .next()
+        .map(|e| e.beschreibung.clone());
+    let naechste_aenderung = data
+        .wiederholen_liste()
+        .next()
+        .map(|e| e.beschreibung.clone());
+
+    let rueckgaengig = {
+        let data = data.clone();
+        Callback::from(move |_: MouseEvent| data.rueckgaengig(1))
+    };
+    let wiederholen = {
+        let data = data.clone();
+        Callback::from(move |_: MouseEvent| data.wiederholen(1))
+    };
+
+    html! {
+        <div class="verlauf_auswahl">
+            <button
+                onclick={ rueckgaengig }
+                disabled={ letzte_aenderung.is_none() }
+                title={ letzte_aenderung.as_ref().map(|b| format!("Rückgängig: {b} (Strg+Z)")) }
+            >{ "↶" }</button>
+            <button
+                onclick={ wiederholen }
+                disabled={ naechste_aenderung.is_none() }
+                title={ naechste_aenderung.as_ref().map(|b| format!("Wiederholen: {b} (Strg+Y)")) }
+            >{ "↷" }</button>
+            <details>
+                <summary>{ "Verlauf" }</summary>
+                <ul>
+                    // Rückgängig gemachte Änderungen stehen über dem aktuellen Stand
+                    { for data.wiederholen_liste().enumerate().rev().map(|(idx, eintrag)| {
+                        let data = data.clone();
+                        let onclick = Callback::from(move |_: MouseEvent| data.wiederholen(idx + 1));
+                        html! {
+                            <li class="rueckgaengig_gemacht"><button { onclick }>{ eintrag.beschreibung.clone() }</button></li>
+                        }
+                    }) }
+                    <li class="aktuell">{ "Aktueller Stand" }</li>
+                    { for data.rueckgaengig_liste().enumerate().map(|(idx, eintrag)| {
+                        let data = data.clone();
+                        let onclick = Callback::from(move |_: MouseEvent| data.rueckgaengig(idx + 1));
+                        html! {
+                            <li><button { onclick }>{ eintrag.beschreibung.clone() }</button></li>
+                        }
+                    }) }
+                </ul>
+            </details>
+        </div>
+    }
+}
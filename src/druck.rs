@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use crate::{Data, types::ProjektId};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Teilnehmer {
+    pub name: String,
+    pub klasse: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Projektliste {
+    pub projekt_id: ProjektId,
+    pub name: String,
+    pub min_stufe: u32,
+    pub max_stufe: u32,
+    pub raum: String,
+    pub betreuer: String,
+    pub teilnehmer: Vec<Teilnehmer>,
+}
+
+// Eine Liste je stattfindendem Projekt, die Teilnehmer sortiert nach Klasse und Name
+pub fn projektlisten(data: &Data) -> Vec<Projektliste> {
+    data.projekte
+        .iter()
+        .filter(|(_, projekt)| !projekt.ignore && !projekt.abgesagt)
+        .map(|(&projekt_id, projekt)| {
+            let mut teilnehmer = data
+                .zuordnung
+                .iter()
+                .filter(|zuordnung| zuordnung.projekt == Some(projekt_id))
+                .filter_map(|zuordnung| data.get_schueler(&zuordnung.schueler))
+                .filter(|schueler| !schueler.ignore)
+                .map(|schueler| Teilnehmer {
+                    name: schueler.name.clone(),
+                    klasse: schueler.klasse.klasse(),
+                })
+                .collect::<Vec<Teilnehmer>>();
+
+            teilnehmer.sort_by(|a, b| (&a.klasse, &a.name).cmp(&(&b.klasse, &b.name)));
+
+            Projektliste {
+                projekt_id,
+                name: projekt.name.clone(),
+                min_stufe: projekt.min_stufe,
+                max_stufe: projekt.max_stufe,
+                raum: projekt.raum.clone(),
+                betreuer: projekt.betreuer.clone(),
+                teilnehmer,
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Einteilung {
+    Projekt { name: String, raum: String },
+    NichtEingeteilt,
+    Ignoriert,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KlassenEintrag {
+    pub name: String,
+    pub einteilung: Einteilung,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Klassenliste {
+    pub klasse: String,
+    pub schueler: Vec<KlassenEintrag>,
+}
+
+// Eine Liste je Klasse mit allen Schülern, auch nicht eingeteilten und ignorierten
+pub fn klassenlisten(data: &Data) -> Vec<Klassenliste> {
+    let mut klassen = BTreeMap::<String, Vec<KlassenEintrag>>::new();
+
+    for (schueler_id, schueler) in &data.schueler {
+        let projekt = data
+            .zuordnung
+            .iter()
+            .find(|zuordnung| zuordnung.schueler == *schueler_id)
+            .and_then(|zuordnung| zuordnung.projekt)
+            .and_then(|projekt_id| {
+                data.get_projekt(&projekt_id)
+                    .map(|projekt| (projekt_id, projekt))
+            });
+
+        let einteilung = match projekt {
+            _ if schueler.ignore => Einteilung::Ignoriert,
+            Some((projekt_id, projekt)) => Einteilung::Projekt {
+                name: format!("{projekt_id}: {}", projekt.name),
+                raum: projekt.raum.clone(),
+            },
+            None => Einteilung::NichtEingeteilt,
+        };
+
+        klassen
+            .entry(schueler.klasse.klasse())
+            .or_default()
+            .push(KlassenEintrag {
+                name: schueler.name.clone(),
+                einteilung,
+            });
+    }
+
+    klassen
+        .into_iter()
+        .map(|(klasse, mut schueler)| {
+            schueler.sort_by_key(|eintrag| eintrag.name.to_lowercase());
+
+            Klassenliste { klasse, schueler }
+        })
+        .collect()
+}
+
+pub const PLATZHALTER: [(&str, &str); 6] = [
+    ("{name}", "Name des Schülers"),
+    ("{klasse}", "Klasse"),
+    ("{projekt}", "Name des Projekts"),
+    ("{raum}", "Raum des Projekts"),
+    ("{betreuer}", "Betreuer des Projekts"),
+    ("{wunsch}", "Erfüllter Wunsch, z.B. \"2. Wunsch\""),
+];
+
+// Ein ausgefüllter Zettel je eingeteiltem Schüler, sortiert nach Klasse und Name
+pub fn zettel(data: &Data) -> Vec<String> {
+    let mut zettel = data
+        .zuordnung
+        .iter()
+        .filter_map(|zuordnung| {
+            let schueler = data.get_schueler(&zuordnung.schueler)?;
+            let projekt_id = zuordnung.projekt?;
+            let projekt = data.get_projekt(&projekt_id)?;
+
+            if schueler.ignore {
+                return None;
+            }
+
+            let wunsch = schueler
+                .wishes
+                .and_then(|wishes| wishes.iter().position(|wish| *wish == projekt_id))
+                .map(|rang| format!("{}. Wunsch", rang + 1))
+                .unwrap_or("nicht gewünscht".to_string());
+
+            let werte = [
+                schueler.name.clone(),
+                schueler.klasse.klasse(),
+                projekt.name.clone(),
+                projekt.raum.clone(),
+                projekt.betreuer.clone(),
+                wunsch,
+            ];
+
+            let text = PLATZHALTER.iter().zip(werte).fold(
+                data.druck.vorlage.clone(),
+                |text, ((platzhalter, _), wert)| text.replace(platzhalter, &wert),
+            );
+
+            Some(((schueler.klasse.klasse(), schueler.name.clone()), text))
+        })
+        .collect::<Vec<((String, String), String)>>();
+
+    zettel.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    zettel.into_iter().map(|(_, text)| text).collect()
+}
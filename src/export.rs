@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use csv::WriterBuilder;
+use gloo::utils::document;
+use gloo_file::{Blob, ObjectUrl};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use web_sys::{HtmlAnchorElement, wasm_bindgen::JsCast};
+
+use crate::{Data, types::ProjektId};
+
+const SPALTEN: [&str; 9] = [
+    "Name",
+    "Klasse",
+    "Projekt",
+    "Erfüllter Wunsch",
+    "1. Wunsch",
+    "2. Wunsch",
+    "3. Wunsch",
+    "4. Wunsch",
+    "5. Wunsch",
+];
+
+#[derive(Debug)]
+pub enum ExportFehler {
+    Xlsx(XlsxError),
+    Csv(csv::Error),
+}
+
+impl fmt::Display for ExportFehler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFehler::Xlsx(fehler) => {
+                write!(f, "Excel-Datei kann nicht erstellt werden: {fehler}")
+            }
+            ExportFehler::Csv(fehler) => {
+                write!(f, "CSV-Datei kann nicht erstellt werden: {fehler}")
+            }
+        }
+    }
+}
+
+impl From<XlsxError> for ExportFehler {
+    fn from(value: XlsxError) -> Self {
+        ExportFehler::Xlsx(value)
+    }
+}
+
+impl From<csv::Error> for ExportFehler {
+    fn from(value: csv::Error) -> Self {
+        ExportFehler::Csv(value)
+    }
+}
+
+struct ExportZeile {
+    name: String,
+    klasse: String,
+    projekt_id: Option<ProjektId>,
+    projekt: String,
+    erfuellt: String,
+    wuensche: [String; 5],
+}
+
+impl ExportZeile {
+    fn felder(&self) -> Vec<&str> {
+        let mut felder = vec![
+            self.name.as_str(),
+            self.klasse.as_str(),
+            self.projekt.as_str(),
+            self.erfuellt.as_str(),
+        ];
+        felder.extend(self.wuensche.iter().map(String::as_str));
+
+        felder
+    }
+}
+
+fn projekt_text(data: &Data, projekt_id: &ProjektId) -> String {
+    match data.get_projekt(projekt_id) {
+        Some(projekt) => format!("{projekt_id}: {}", projekt.name),
+        None => format!("{projekt_id}: unbekannt"),
+    }
+}
+
+// Alle eingeteilten Schüler, sortiert nach Klasse und Name
+fn zeilen(data: &Data) -> Vec<ExportZeile> {
+    let mut zeilen = data
+        .zuordnung
+        .iter()
+        .filter_map(|zuordnung| {
+            let schueler = data.get_schueler(&zuordnung.schueler)?;
+
+            let wishes = schueler.wishes.unwrap_or([ProjektId::KEIN_WUNSCH; 5]);
+            let erfuellt = match zuordnung.projekt {
+                Some(projekt_id) => match wishes.iter().position(|wish| *wish == projekt_id) {
+                    Some(rang) => format!("{}. Wunsch", rang + 1),
+                    None => "nicht gewünscht".to_string(),
+                },
+                None => String::new(),
+            };
+
+            Some(ExportZeile {
+                name: schueler.name.clone(),
+                klasse: schueler.klasse.klasse(),
+                projekt_id: zuordnung.projekt,
+                projekt: zuordnung
+                    .projekt
+                    .map(|projekt_id| projekt_text(data, &projekt_id))
+                    .unwrap_or_default(),
+                erfuellt,
+                wuensche: wishes.map(|wish| {
+                    if wish == ProjektId::KEIN_WUNSCH {
+                        String::new()
+                    } else {
+                        projekt_text(data, &wish)
+                    }
+                }),
+            })
+        })
+        .collect::<Vec<ExportZeile>>();
+
+    zeilen.sort_by(|a, b| (&a.klasse, &a.name).cmp(&(&b.klasse, &b.name)));
+
+    zeilen
+}
+
+// Excel erlaubt höchstens 31 Zeichen ohne []:*?/\ und keine doppelten Namen
+fn blattname(name: &str, vergeben: &mut Vec<String>) -> String {
+    let bereinigt = name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .collect::<String>();
+    let bereinigt = bereinigt.trim_matches('\'').trim();
+    let basis = if bereinigt.is_empty() {
+        "Blatt".to_string()
+    } else {
+        bereinigt.chars().take(31).collect::<String>()
+    };
+
+    let belegt = |kandidat: &str| {
+        kandidat.eq_ignore_ascii_case("History")
+            || vergeben
+                .iter()
+                .any(|name| name.to_lowercase() == kandidat.to_lowercase())
+    };
+
+    let mut kandidat = basis.clone();
+    let mut nummer = 2;
+    while belegt(&kandidat) {
+        let zusatz = format!(" ({nummer})");
+        kandidat = basis
+            .chars()
+            .take(31 - zusatz.chars().count())
+            .collect::<String>()
+            + &zusatz;
+        nummer += 1;
+    }
+
+    vergeben.push(kandidat.clone());
+
+    kandidat
+}
+
+fn tabellenblatt<'a>(
+    workbook: &mut Workbook,
+    vergeben: &mut Vec<String>,
+    name: &str,
+    zeilen: impl Iterator<Item = &'a ExportZeile>,
+) -> Result<(), XlsxError> {
+    let fett = Format::new().set_bold();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(blattname(name, vergeben))?;
+
+    for (spalte, titel) in SPALTEN.iter().enumerate() {
+        worksheet.write_string_with_format(0, spalte as u16, *titel, &fett)?;
+    }
+
+    for (idx, zeile) in zeilen.enumerate() {
+        for (spalte, feld) in zeile.felder().into_iter().enumerate() {
+            worksheet.write_string(idx as u32 + 1, spalte as u16, feld)?;
+        }
+    }
+
+    worksheet.set_freeze_panes(1, 0)?;
+    worksheet.autofit();
+
+    Ok(())
+}
+
+// Ein Blatt mit allen Schülern, dann je ein Blatt pro Projekt und pro Klasse
+pub fn xlsx_exportieren(data: &Data) -> Result<Vec<u8>, ExportFehler> {
+    let zeilen = zeilen(data);
+
+    let mut workbook = Workbook::new();
+    let mut vergeben = Vec::new();
+
+    tabellenblatt(&mut workbook, &mut vergeben, "Alle Schüler", zeilen.iter())?;
+
+    for (projekt_id, projekt) in &data.projekte {
+        if projekt.ignore || projekt.abgesagt {
+            continue;
+        }
+
+        tabellenblatt(
+            &mut workbook,
+            &mut vergeben,
+            &format!("{projekt_id} {}", projekt.name),
+            zeilen.iter().filter(|z| z.projekt_id == Some(*projekt_id)),
+        )?;
+    }
+
+    if zeilen.iter().any(|z| z.projekt_id.is_none()) {
+        tabellenblatt(
+            &mut workbook,
+            &mut vergeben,
+            "Nicht eingeteilt",
+            zeilen.iter().filter(|z| z.projekt_id.is_none()),
+        )?;
+    }
+
+    let klassen = zeilen
+        .iter()
+        .map(|z| z.klasse.clone())
+        .collect::<BTreeSet<String>>();
+
+    for klasse in klassen {
+        tabellenblatt(
+            &mut workbook,
+            &mut vergeben,
+            &format!("Klasse {klasse}"),
+            zeilen.iter().filter(|z| z.klasse == klasse),
+        )?;
+    }
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+pub fn csv_exportieren(data: &Data) -> Result<Vec<u8>, ExportFehler> {
+    // Ohne BOM liest Excel die Datei nicht als UTF-8 und Umlaute gehen kaputt
+    let mut inhalt = "\u{FEFF}".as_bytes().to_vec();
+
+    {
+        let mut writer = WriterBuilder::new()
+            .delimiter(b';')
+            .from_writer(&mut inhalt);
+
+        writer.write_record(SPALTEN)?;
+        for zeile in zeilen(data) {
+            writer.write_record(zeile.felder())?;
+        }
+        writer.flush().map_err(csv::Error::from)?;
+    }
+
+    Ok(inhalt)
+}
+
+pub fn herunterladen(dateiname: &str, mime_type: &str, inhalt: &[u8]) {
+    let blob = Blob::new_with_options(inhalt, Some(mime_type));
+    let url = ObjectUrl::from(blob);
+
+    let Some(a) = document()
+        .create_element("a")
+        .ok()
+        .and_then(|a| a.dyn_into::<HtmlAnchorElement>().ok())
+    else {
+        return;
+    };
+
+    a.set_href(&url);
+    a.set_download(dateiname);
+    a.click();
+}
@@ -0,0 +1,197 @@
+pub mod projekte;
+pub mod schueler;
+pub mod vorschau;
+
+use std::fmt;
+use std::io::Cursor;
+
+use calamine::{Reader, open_workbook_auto_from_rs};
+use csv::ReaderBuilder;
+use unicode_normalization::UnicodeNormalization;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImportFeld {
+    pub name: &'static str,
+    pub pflicht: bool,
+    // Spaltenköpfe, die beim automatischen Zuordnen erkannt werden
+    pub aliase: &'static [&'static str],
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbgelehnteZeile {
+    pub zeile: usize,
+    pub grund: String,
+}
+
+#[derive(Debug)]
+pub enum ImportFehler {
+    Csv(csv::Error),
+    Tabelle(calamine::Error),
+    KeinTabellenblatt,
+    Leer,
+}
+
+impl fmt::Display for ImportFehler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFehler::Csv(fehler) => write!(f, "CSV-Datei kann nicht gelesen werden: {fehler}"),
+            ImportFehler::Tabelle(fehler) => {
+                write!(f, "Tabelle kann nicht gelesen werden: {fehler}")
+            }
+            ImportFehler::KeinTabellenblatt => write!(f, "Die Tabelle enthält kein Tabellenblatt"),
+            ImportFehler::Leer => write!(f, "Die Datei enthält keine Daten"),
+        }
+    }
+}
+
+impl From<csv::Error> for ImportFehler {
+    fn from(value: csv::Error) -> Self {
+        ImportFehler::Csv(value)
+    }
+}
+
+impl From<calamine::Error> for ImportFehler {
+    fn from(value: calamine::Error) -> Self {
+        ImportFehler::Tabelle(value)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImportTabelle {
+    pub kopf: Vec<String>,
+    pub zeilen: Vec<Vec<String>>,
+}
+
+impl ImportTabelle {
+    pub fn lesen(dateiname: &str, bytes: &[u8]) -> Result<Self, ImportFehler> {
+        let endung = dateiname
+            .rsplit_once('.')
+            .map(|(_, endung)| endung.to_lowercase())
+            .unwrap_or_default();
+
+        let mut zeilen = match endung.as_str() {
+            "xlsx" | "xlsm" | "xlsb" | "xls" | "ods" => Self::zeilen_aus_tabelle(bytes)?,
+            _ => Self::zeilen_aus_csv(bytes)?,
+        };
+
+        zeilen.retain(|zeile| zeile.iter().any(|zelle| !zelle.trim().is_empty()));
+
+        if zeilen.is_empty() {
+            return Err(ImportFehler::Leer);
+        }
+
+        let kopf = zeilen.remove(0);
+
+        Ok(Self { kopf, zeilen })
+    }
+
+    fn zeilen_aus_csv(bytes: &[u8]) -> Result<Vec<Vec<String>>, ImportFehler> {
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_start_matches('\u{feff}');
+
+        // Trennzeichen anhand der ersten Zeile raten (Excel speichert in Deutschland mit ;).
+        // max_by_key liefert das letzte Maximum, rückwärts gewinnt bei Gleichstand also ;
+        let erste_zeile = text.lines().next().unwrap_or_default();
+        let trennzeichen = [b';', b',', b'\t']
+            .into_iter()
+            .rev()
+            .max_by_key(|&zeichen| erste_zeile.bytes().filter(|&b| b == zeichen).count())
+            .unwrap_or(b';');
+
+        let mut reader = ReaderBuilder::new()
+            .delimiter(trennzeichen)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+
+        let mut zeilen = Vec::new();
+        for record in reader.records() {
+            zeilen.push(
+                record?
+                    .iter()
+                    .map(|zelle| zelle.trim().to_string())
+                    .collect(),
+            );
+        }
+
+        Ok(zeilen)
+    }
+
+    fn zeilen_aus_tabelle(bytes: &[u8]) -> Result<Vec<Vec<String>>, ImportFehler> {
+        let mut mappe = open_workbook_auto_from_rs(Cursor::new(bytes.to_vec()))?;
+
+        let blatt = mappe
+            .worksheet_range_at(0)
+            .ok_or(ImportFehler::KeinTabellenblatt)??;
+
+        Ok(blatt
+            .rows()
+            .map(|zeile| {
+                zeile
+                    .iter()
+                    .map(|zelle| zelle.to_string().trim().to_string())
+                    .collect()
+            })
+            .collect())
+    }
+
+    pub fn zelle(&self, zeile: &[String], spalte: Option<usize>) -> Option<String> {
+        spalte
+            .and_then(|spalte| zeile.get(spalte))
+            .map(|zelle| zelle.trim().to_string())
+            .filter(|zelle| !zelle.is_empty())
+    }
+
+    pub fn zuordnung_raten(&self, felder: &[ImportFeld]) -> Vec<Option<usize>> {
+        let kopf = self
+            .kopf
+            .iter()
+            .map(|spalte| normalisieren(spalte))
+            .collect::<Vec<String>>();
+
+        felder
+            .iter()
+            .map(|feld| {
+                kopf.iter().position(|spalte| {
+                    feld.aliase
+                        .iter()
+                        .any(|alias| *spalte == normalisieren(alias))
+                })
+            })
+            .collect()
+    }
+
+    // Spalten, die zu einem der Aliase passen und noch keinem Feld zugeordnet sind
+    pub fn spalten_raten(&self, aliase: &[&str], zuordnung: &[Option<usize>]) -> Vec<usize> {
+        self.kopf
+            .iter()
+            .enumerate()
+            .filter(|&(spalte, kopf)| {
+                !zuordnung.contains(&Some(spalte))
+                    && aliase
+                        .iter()
+                        .any(|alias| normalisieren(kopf) == normalisieren(alias))
+            })
+            .map(|(spalte, _)| spalte)
+            .collect()
+    }
+}
+
+fn normalisieren(text: &str) -> String {
+    text.nfkd()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// Zahlen aus Tabellen kommen auch als "12.0" an
+pub fn zahl_lesen(text: &str) -> Option<i32> {
+    let text = text.trim();
+
+    str::parse::<i32>(text).ok().or_else(|| {
+        str::parse::<f64>(&text.replace(',', "."))
+            .ok()
+            .filter(|zahl| zahl.fract() == 0.0)
+            .map(|zahl| zahl as i32)
+    })
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    import::{AbgelehnteZeile, ImportFeld, ImportTabelle, zahl_lesen},
+    types::{ProjektId, SaveFileProjekt},
+};
+
+pub const FELDER: &[ImportFeld] = &[
+    ImportFeld {
+        name: "Name",
+        pflicht: true,
+        aliase: &["Name", "Projekt", "Projektname", "Titel"],
+    },
+    ImportFeld {
+        name: "Mindeste Stufe",
+        pflicht: true,
+        aliase: &[
+            "min_stufe",
+            "Min Stufe",
+            "Mindeste Stufe",
+            "Stufe von",
+            "von",
+        ],
+    },
+    ImportFeld {
+        name: "Maximale Stufe",
+        pflicht: true,
+        aliase: &[
+            "max_stufe",
+            "Max Stufe",
+            "Maximale Stufe",
+            "Stufe bis",
+            "bis",
+        ],
+    },
+    ImportFeld {
+        name: "Mindeste Teilnehmeranzahl",
+        pflicht: false,
+        aliase: &[
+            "min_teilnehmer",
+            "min",
+            "Min Teilnehmer",
+            "Mindeste Teilnehmeranzahl",
+            "Mindestteilnehmer",
+        ],
+    },
+    ImportFeld {
+        name: "Maximale Teilnehmeranzahl",
+        pflicht: false,
+        aliase: &[
+            "max_teilnehmer",
+            "max",
+            "Max Teilnehmer",
+            "Maximale Teilnehmeranzahl",
+            "Plätze",
+        ],
+    },
+    ImportFeld {
+        name: "Raum",
+        pflicht: false,
+        aliase: &["Raum", "Ort", "Raumnummer"],
+    },
+    ImportFeld {
+        name: "Betreuer",
+        pflicht: false,
+        aliase: &["Betreuer", "Betreuung", "Lehrkraft", "Leitung"],
+    },
+];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImportProjekt {
+    pub name: String,
+    pub min_stufe: u32,
+    pub max_stufe: u32,
+    pub min_teilnehmer: i32,
+    pub max_teilnehmer: i32,
+    pub raum: String,
+    pub betreuer: String,
+}
+
+impl From<SaveFileProjekt> for ImportProjekt {
+    fn from(value: SaveFileProjekt) -> Self {
+        Self {
+            name: value.name,
+            min_stufe: value.min_stufe,
+            max_stufe: value.max_stufe,
+            min_teilnehmer: value.min_teilnehmer,
+            max_teilnehmer: value.max_teilnehmer,
+            raum: value.raum,
+            betreuer: value.betreuer,
+        }
+    }
+}
+
+pub fn projekte_lesen(
+    tabelle: &ImportTabelle,
+    zuordnung: &[Option<usize>],
+) -> (Vec<ImportProjekt>, Vec<AbgelehnteZeile>) {
+    let spalte = |idx: usize| zuordnung.get(idx).copied().flatten();
+
+    let mut projekte = Vec::new();
+    let mut abgelehnt = Vec::new();
+    let mut namen = BTreeSet::new();
+
+    for (idx, zeile) in tabelle.zeilen.iter().enumerate() {
+        // Zeile 1 ist der Tabellenkopf
+        let nummer = idx + 2;
+
+        match projekt_lesen(tabelle, zeile, spalte) {
+            Ok(projekt) if !namen.insert(projekt.name.to_lowercase()) => {
+                abgelehnt.push(AbgelehnteZeile {
+                    zeile: nummer,
+                    grund: format!("\"{}\" kommt mehrfach vor", projekt.name),
+                })
+            }
+            Ok(projekt) => projekte.push(projekt),
+            Err(grund) => abgelehnt.push(AbgelehnteZeile {
+                zeile: nummer,
+                grund,
+            }),
+        }
+    }
+
+    (projekte, abgelehnt)
+}
+
+fn projekt_lesen(
+    tabelle: &ImportTabelle,
+    zeile: &[String],
+    spalte: impl Fn(usize) -> Option<usize>,
+) -> Result<ImportProjekt, String> {
+    let name = tabelle
+        .zelle(zeile, spalte(0))
+        .ok_or("Kein Projektname".to_string())?;
+
+    let stufe = |idx: usize| {
+        let text = tabelle
+            .zelle(zeile, spalte(idx))
+            .ok_or(format!("{}: keine Angabe", FELDER[idx].name))?;
+
+        zahl_lesen(&text)
+            .filter(|&stufe| stufe >= 0)
+            .map(|stufe| stufe as u32)
+            .ok_or(format!("{}: \"{text}\" ist keine Stufe", FELDER[idx].name))
+    };
+
+    // Leere Teilnehmergrenzen bedeuten "keine Grenze" (-1)
+    let teilnehmer = |idx: usize| match tabelle.zelle(zeile, spalte(idx)) {
+        None => Ok(-1),
+        Some(text) => zahl_lesen(&text)
+            .filter(|&anzahl| anzahl >= 0)
+            .ok_or(format!("{}: \"{text}\" ist keine Anzahl", FELDER[idx].name)),
+    };
+
+    let projekt = ImportProjekt {
+        name,
+        min_stufe: stufe(1)?,
+        max_stufe: stufe(2)?,
+        min_teilnehmer: teilnehmer(3)?,
+        max_teilnehmer: teilnehmer(4)?,
+        raum: tabelle.zelle(zeile, spalte(5)).unwrap_or_default(),
+        betreuer: tabelle.zelle(zeile, spalte(6)).unwrap_or_default(),
+    };
+
+    if projekt.min_stufe > projekt.max_stufe {
+        return Err(format!(
+            "Mindeste Stufe {} liegt über der maximalen Stufe {}",
+            projekt.min_stufe, projekt.max_stufe
+        ));
+    }
+
+    if projekt.max_teilnehmer != -1 && projekt.min_teilnehmer > projekt.max_teilnehmer {
+        return Err(format!(
+            "Mindestens {} Teilnehmer, aber höchstens {}",
+            projekt.min_teilnehmer, projekt.max_teilnehmer
+        ));
+    }
+
+    Ok(projekt)
+}
+
+// Projekte werden über den Namen zugeordnet, bestehende behalten ID, Einteilung und Status
+pub fn projekte_zusammenfuehren(
+    projekte: &mut BTreeMap<ProjektId, SaveFileProjekt>,
+    neue_projekte: Vec<ImportProjekt>,
+) -> (usize, usize) {
+    let mut neu = 0;
+    let mut aktualisiert = 0;
+
+    for import in neue_projekte {
+        let bestehend = projekte
+            .values_mut()
+            .find(|projekt| projekt.name.trim().to_lowercase() == import.name.to_lowercase());
+
+        if let Some(projekt) = bestehend {
+            projekt.min_stufe = import.min_stufe;
+            projekt.max_stufe = import.max_stufe;
+            projekt.min_teilnehmer = import.min_teilnehmer;
+            projekt.max_teilnehmer = import.max_teilnehmer;
+            // Ohne Raum- oder Betreuerspalte bleiben die bisherigen Angaben erhalten
+            if !import.raum.is_empty() {
+                projekt.raum = import.raum;
+            }
+            if !import.betreuer.is_empty() {
+                projekt.betreuer = import.betreuer;
+            }
+
+            aktualisiert += 1;
+        } else {
+            let projekt_id = projekte
+                .keys()
+                .last()
+                .map(|p_id| ProjektId::new(p_id.id() + 1))
+                .unwrap_or_default();
+
+            projekte.insert(
+                projekt_id,
+                SaveFileProjekt {
+                    name: import.name,
+                    min_teilnehmer: import.min_teilnehmer,
+                    max_teilnehmer: import.max_teilnehmer,
+                    min_stufe: import.min_stufe,
+                    max_stufe: import.max_stufe,
+                    ignore: false,
+                    num_einteilung: None,
+                    abgesagt: false,
+                    raum: import.raum,
+                    betreuer: import.betreuer,
+                    quoten: Vec::new(),
+                    max_pro_klasse: None,
+                    anteile: Vec::new(),
+                },
+            );
+
+            neu += 1;
+        }
+    }
+
+    (neu, aktualisiert)
+}
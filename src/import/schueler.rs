@@ -0,0 +1,362 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    import::{AbgelehnteZeile, ImportFeld, ImportTabelle, zahl_lesen},
+    types::{Klasse, ProjektId, SaveFileProjekt, SaveFileSchueler, SchuelerId},
+};
+
+pub const FELDER: &[ImportFeld] = &[
+    ImportFeld {
+        name: "Name",
+        pflicht: true,
+        aliase: &["Name", "Vollständiger Name", "Schüler", "Schueler"],
+    },
+    ImportFeld {
+        name: "Klasse",
+        pflicht: true,
+        aliase: &["Klasse", "Gruppe", "Gruppen"],
+    },
+    ImportFeld {
+        name: "1. Wunsch",
+        pflicht: false,
+        aliase: &["Wunsch 1", "1. Wunsch", "Erstwunsch"],
+    },
+    ImportFeld {
+        name: "2. Wunsch",
+        pflicht: false,
+        aliase: &["Wunsch 2", "2. Wunsch", "Zweitwunsch"],
+    },
+    ImportFeld {
+        name: "3. Wunsch",
+        pflicht: false,
+        aliase: &["Wunsch 3", "3. Wunsch", "Drittwunsch"],
+    },
+    ImportFeld {
+        name: "4. Wunsch",
+        pflicht: false,
+        aliase: &["Wunsch 4", "4. Wunsch", "Viertwunsch"],
+    },
+    ImportFeld {
+        name: "5. Wunsch",
+        pflicht: false,
+        aliase: &["Wunsch 5", "5. Wunsch", "Fünftwunsch"],
+    },
+    ImportFeld {
+        name: "Wunschpartner",
+        pflicht: false,
+        aliase: &["Wunschpartner", "Partner"],
+    },
+];
+
+// Spalten, die beim Import als Merkmal vorgeschlagen werden
+pub const MERKMAL_ALIASE: &[&str] = &["Geschlecht", "Förderbedarf", "Foerderbedarf"];
+
+// Moodle kürzt Spaltennamen auf Kleinbuchstaben ohne Umlaute ("vollstndigername"),
+// Fragen der Wahl bekommen zusätzlich ein Präfix wie "q07_"
+pub fn merkmale_aus_feldern(felder: &BTreeMap<String, Value>) -> BTreeMap<String, String> {
+    let normalisieren = |text: &str| {
+        text.chars()
+            .filter(char::is_ascii_alphabetic)
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+
+    felder
+        .iter()
+        .filter_map(|(feld, wert)| {
+            let feld = normalisieren(feld);
+            let merkmal = MERKMAL_ALIASE
+                .iter()
+                .find(|alias| feld.ends_with(&normalisieren(alias)))?;
+            let wert = match wert {
+                Value::String(text) => text.trim().to_string(),
+                Value::Number(zahl) => zahl.to_string(),
+                _ => return None,
+            };
+
+            (!wert.is_empty()).then(|| (merkmal.to_string(), wert))
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImportSchueler {
+    pub name: String,
+    pub klasse: Klasse,
+    pub wishes: Option<[ProjektId; 5]>,
+    pub partner_raw: Option<String>,
+    pub merkmale: BTreeMap<String, String>,
+}
+
+pub fn schueler_lesen(
+    tabelle: &ImportTabelle,
+    zuordnung: &[Option<usize>],
+    merkmal_spalten: &[usize],
+    projekte: &BTreeMap<ProjektId, SaveFileProjekt>,
+) -> (Vec<ImportSchueler>, Vec<AbgelehnteZeile>) {
+    let spalte = |idx: usize| zuordnung.get(idx).copied().flatten();
+
+    let mut schueler = Vec::new();
+    let mut abgelehnt = Vec::new();
+    let mut namen = BTreeSet::new();
+
+    for (idx, zeile) in tabelle.zeilen.iter().enumerate() {
+        // Zeile 1 ist der Tabellenkopf
+        let nummer = idx + 2;
+
+        match schueler_zeile_lesen(tabelle, zeile, spalte, merkmal_spalten, projekte) {
+            Ok(s) if !namen.insert(schluessel(&s.name, &s.klasse)) => {
+                abgelehnt.push(AbgelehnteZeile {
+                    zeile: nummer,
+                    grund: format!("{} ({}) kommt mehrfach vor", s.name, s.klasse.klasse()),
+                })
+            }
+            Ok(s) => schueler.push(s),
+            Err(grund) => abgelehnt.push(AbgelehnteZeile {
+                zeile: nummer,
+                grund,
+            }),
+        }
+    }
+
+    (schueler, abgelehnt)
+}
+
+fn schueler_zeile_lesen(
+    tabelle: &ImportTabelle,
+    zeile: &[String],
+    spalte: impl Fn(usize) -> Option<usize>,
+    merkmal_spalten: &[usize],
+    projekte: &BTreeMap<ProjektId, SaveFileProjekt>,
+) -> Result<ImportSchueler, String> {
+    let name = tabelle
+        .zelle(zeile, spalte(0))
+        .ok_or("Kein Name".to_string())?;
+    let klasse = tabelle
+        .zelle(zeile, spalte(1))
+        .ok_or(format!("{name}: keine Klasse"))?;
+
+    let mut wuensche = [None; 5];
+    for (wunsch_idx, wunsch) in wuensche.iter_mut().enumerate() {
+        let Some(text) = tabelle.zelle(zeile, spalte(wunsch_idx + 2)) else {
+            continue;
+        };
+
+        *wunsch = Some(projekt_finden(&text, projekte).ok_or(format!(
+            "{}: Projekt \"{text}\" nicht gefunden",
+            FELDER[wunsch_idx + 2].name
+        ))?);
+    }
+
+    // Fehlende Wünsche werden wie im Schüler-Editor als "Kein Wunsch" (-1) gespeichert
+    let wishes = wuensche
+        .iter()
+        .any(Option::is_some)
+        .then(|| wuensche.map(|wunsch| wunsch.unwrap_or(ProjektId::KEIN_WUNSCH)));
+
+    Ok(ImportSchueler {
+        name,
+        klasse: Klasse::new(klasse),
+        wishes,
+        partner_raw: tabelle.zelle(zeile, spalte(7)),
+        // Der Spaltenkopf wird zum Namen des Merkmals
+        merkmale: merkmal_spalten
+            .iter()
+            .filter_map(|&merkmal_spalte| {
+                Some((
+                    tabelle.kopf.get(merkmal_spalte)?.trim().to_string(),
+                    tabelle.zelle(zeile, Some(merkmal_spalte))?,
+                ))
+            })
+            .collect(),
+    })
+}
+
+// Wünsche stehen als Projektnummer ("3" oder "3 : Name" wie im Moodle-Export) oder als Projektname in der Tabelle
+fn projekt_finden(
+    text: &str,
+    projekte: &BTreeMap<ProjektId, SaveFileProjekt>,
+) -> Option<ProjektId> {
+    let nummer = text.split(" : ").next().and_then(zahl_lesen);
+
+    // Nummern ohne passendes Projekt werden wie unbekannte Namen abgelehnt
+    if let Some(nummer) = nummer.filter(|&nummer| nummer > 0) {
+        let projekt_id = ProjektId::new(nummer as u32 - 1);
+        return projekte.contains_key(&projekt_id).then_some(projekt_id);
+    }
+
+    projekte
+        .iter()
+        .find(|(_, projekt)| projekt.name.trim().to_lowercase() == text.to_lowercase())
+        .map(|(&p_id, _)| p_id)
+}
+
+fn schluessel(name: &str, klasse: &Klasse) -> (String, String) {
+    (
+        name.trim().to_lowercase(),
+        klasse.klasse().trim().to_lowercase(),
+    )
+}
+
+// Tabellenzeilen haben keine ID und UID. Sie bekommen neue, die keinen bestehenden Schüler treffen,
+// damit der Abgleich über Name und Klasse zuordnet.
+pub fn wahl_aus_tabelle(
+    schueler: &BTreeMap<SchuelerId, SaveFileSchueler>,
+    neue_schueler: Vec<ImportSchueler>,
+) -> BTreeMap<SchuelerId, SaveFileSchueler> {
+    let erste_uid = schueler
+        .values()
+        .map(|s| s.uid + 1)
+        .max()
+        .unwrap_or_default();
+
+    neue_schueler
+        .into_iter()
+        .enumerate()
+        .map(|(idx, import)| {
+            (
+                SchuelerId::new(Uuid::new_v4()),
+                SaveFileSchueler {
+                    uid: erste_uid + idx as u32,
+                    name: import.name,
+                    wishes: import.wishes,
+                    partner_raw: import.partner_raw,
+                    ignore: false,
+                    fest: Some(false),
+                    klasse: import.klasse,
+                    partner: None,
+                    merkmale: import.merkmale,
+                },
+            )
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Abgleich {
+    pub neu: usize,
+    pub zugeordnet: usize,
+    // Schüler, die nur auf einer Seite vorkommen, als "Name (Klasse)"
+    pub fehlend: Vec<String>,
+}
+
+fn anzeigename(schueler: &SaveFileSchueler) -> String {
+    format!("{} ({})", schueler.name, schueler.klasse.klasse())
+}
+
+// Zuordnung über die ID, dann über die UID und zuletzt über Name und Klasse
+fn schueler_finden(
+    schueler: &BTreeMap<SchuelerId, SaveFileSchueler>,
+    vergeben: &BTreeSet<SchuelerId>,
+    schueler_id: &SchuelerId,
+    import: &SaveFileSchueler,
+) -> Option<SchuelerId> {
+    let frei = |id: &SchuelerId| !vergeben.contains(id);
+
+    if schueler.contains_key(schueler_id) && frei(schueler_id) {
+        return Some(*schueler_id);
+    }
+
+    schueler
+        .iter()
+        .filter(|(id, _)| frei(id))
+        .find(|(_, s)| s.uid == import.uid)
+        .or_else(|| {
+            schueler.iter().filter(|(id, _)| frei(id)).find(|(_, s)| {
+                schluessel(&s.name, &s.klasse) == schluessel(&import.name, &import.klasse)
+            })
+        })
+        .map(|(&id, _)| id)
+}
+
+fn schueler_abgleichen(
+    schueler: &mut BTreeMap<SchuelerId, SaveFileSchueler>,
+    importiert: BTreeMap<SchuelerId, SaveFileSchueler>,
+    uebernehmen: impl Fn(&mut SaveFileSchueler, SaveFileSchueler),
+) -> (Abgleich, BTreeSet<SchuelerId>, Vec<String>) {
+    let mut abgleich = Abgleich::default();
+    let mut vergeben = BTreeSet::new();
+    let mut ohne_treffer = Vec::new();
+
+    for (schueler_id, import) in importiert {
+        match schueler_finden(schueler, &vergeben, &schueler_id, &import) {
+            Some(id) => {
+                vergeben.insert(id);
+                if let Some(bestehend) = schueler.get_mut(&id) {
+                    uebernehmen(bestehend, import);
+                }
+                abgleich.zugeordnet += 1;
+            }
+            None => {
+                // Die ID ist schon an einen anders zugeordneten Schüler vergeben
+                let schueler_id = if schueler.contains_key(&schueler_id) {
+                    SchuelerId::new(Uuid::new_v4())
+                } else {
+                    schueler_id
+                };
+
+                ohne_treffer.push(anzeigename(&import));
+                vergeben.insert(schueler_id);
+                schueler.insert(schueler_id, import);
+                abgleich.neu += 1;
+            }
+        }
+    }
+
+    (abgleich, vergeben, ohne_treffer)
+}
+
+// Die Schülerliste gibt Name, Klasse, UID und Merkmale vor, Wünsche, Partner und Status bleiben erhalten
+pub fn schuelerliste_abgleichen(
+    schueler: &mut BTreeMap<SchuelerId, SaveFileSchueler>,
+    liste: BTreeMap<SchuelerId, SaveFileSchueler>,
+) -> Abgleich {
+    let (mut abgleich, vergeben, _) = schueler_abgleichen(schueler, liste, |bestehend, neu| {
+        bestehend.name = neu.name;
+        bestehend.klasse = neu.klasse;
+        bestehend.uid = neu.uid;
+        bestehend.merkmale.extend(neu.merkmale);
+    });
+
+    // Schüler mit Wahl, die in der Schülerliste fehlen
+    abgleich.fehlend = schueler
+        .iter()
+        .filter(|(id, s)| !vergeben.contains(id) && s.wishes.is_some())
+        .map(|(_, s)| anzeigename(s))
+        .collect();
+
+    abgleich
+}
+
+// Die Wahl setzt Wünsche, Wunschpartner und Merkmale, Name, Klasse und Status bleiben erhalten
+pub fn wahl_abgleichen(
+    schueler: &mut BTreeMap<SchuelerId, SaveFileSchueler>,
+    wahl: BTreeMap<SchuelerId, SaveFileSchueler>,
+) -> Abgleich {
+    let liste_vorhanden = !schueler.is_empty();
+
+    let (mut abgleich, _, ohne_treffer) = schueler_abgleichen(schueler, wahl, |bestehend, neu| {
+        // Schüler aus der Schülerliste sind ohne Wahl ignoriert
+        if bestehend.wishes.is_none() && neu.wishes.is_some() {
+            bestehend.ignore = false;
+        }
+        if neu.wishes.is_some() {
+            bestehend.wishes = neu.wishes;
+        }
+        // Ohne Partnerangabe in der Wahl bleiben Wunschpartner erhalten
+        if neu.partner_raw.is_some() && bestehend.partner_raw != neu.partner_raw {
+            bestehend.partner_raw = neu.partner_raw;
+            bestehend.partner = None;
+        }
+        bestehend.merkmale.extend(neu.merkmale);
+    });
+
+    if liste_vorhanden {
+        abgleich.fehlend = ohne_treffer;
+    }
+
+    abgleich
+}
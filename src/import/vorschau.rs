@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    Data,
+    types::{Klasse, ProjektId, SaveFileProjekt, SaveFileSchueler, SchuelerId},
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Aenderung {
+    SchuelerNeu {
+        schueler_id: SchuelerId,
+        schueler: SaveFileSchueler,
+    },
+    SchuelerEntfernt {
+        schueler_id: SchuelerId,
+        name: String,
+    },
+    KlasseGeaendert {
+        schueler_id: SchuelerId,
+        name: String,
+        alt: Klasse,
+        neu: Klasse,
+    },
+    WuenscheGeaendert {
+        schueler_id: SchuelerId,
+        name: String,
+        alt: Option<[ProjektId; 5]>,
+        neu: Option<[ProjektId; 5]>,
+    },
+    // Name, Partner, Ignorieren usw.
+    SchuelerGeaendert {
+        schueler_id: SchuelerId,
+        schueler: SaveFileSchueler,
+    },
+    ProjektNeu {
+        projekt_id: ProjektId,
+        projekt: SaveFileProjekt,
+    },
+    ProjektEntfernt {
+        projekt_id: ProjektId,
+        name: String,
+    },
+    ProjektGeaendert {
+        projekt_id: ProjektId,
+        alt: SaveFileProjekt,
+        neu: SaveFileProjekt,
+    },
+    // Einteilung, feste Zuordnungen, Einstellungen und Snapshots aus einem Speicherstand
+    Speicherstand,
+}
+
+impl Aenderung {
+    pub fn beschreibung(&self, projekte: &BTreeMap<ProjektId, SaveFileProjekt>) -> String {
+        match self {
+            Aenderung::SchuelerNeu { schueler, .. } => format!(
+                "Neuer Schüler: {} ({}), Wünsche: {}",
+                schueler.name,
+                schueler.klasse.klasse(),
+                wuensche_text(schueler.wishes, projekte)
+            ),
+            Aenderung::SchuelerEntfernt { name, .. } => format!("Schüler entfernt: {name}"),
+            Aenderung::KlasseGeaendert { name, alt, neu, .. } => {
+                format!("{name}: Klasse {} → {}", alt.klasse(), neu.klasse())
+            }
+            Aenderung::WuenscheGeaendert { name, alt, neu, .. } => format!(
+                "{name}: Wünsche {} → {}",
+                wuensche_text(*alt, projekte),
+                wuensche_text(*neu, projekte)
+            ),
+            Aenderung::SchuelerGeaendert { schueler, .. } => format!(
+                "{} ({}): Name, Partner, Merkmale oder Status geändert",
+                schueler.name,
+                schueler.klasse.klasse()
+            ),
+            Aenderung::ProjektNeu {
+                projekt_id,
+                projekt,
+            } => format!(
+                "Neues Projekt {projekt_id}: {} (Stufe {}-{})",
+                projekt.name, projekt.min_stufe, projekt.max_stufe
+            ),
+            Aenderung::ProjektEntfernt { projekt_id, name } => {
+                format!("Projekt entfernt: {projekt_id}: {name}")
+            }
+            Aenderung::ProjektGeaendert {
+                projekt_id,
+                alt,
+                neu,
+            } => format!(
+                "Projekt {projekt_id}: {} (Stufe {}-{}, {}-{} Teilnehmer) → {} (Stufe {}-{}, {}-{} Teilnehmer)",
+                alt.name,
+                alt.min_stufe,
+                alt.max_stufe,
+                alt.min_teilnehmer,
+                alt.max_teilnehmer,
+                neu.name,
+                neu.min_stufe,
+                neu.max_stufe,
+                neu.min_teilnehmer,
+                neu.max_teilnehmer
+            ),
+            Aenderung::Speicherstand => {
+                "Einteilung, feste Zuordnungen, Einstellungen und Snapshots aus dem Speicherstand übernehmen"
+                    .to_string()
+            }
+        }
+    }
+
+    // Wünsche, die auf Projekte verweisen, die es nach dem Import nicht gibt
+    pub fn unbekannte_wuensche(
+        &self,
+        projekte: &BTreeMap<ProjektId, SaveFileProjekt>,
+    ) -> Vec<ProjektId> {
+        let wishes = match self {
+            Aenderung::SchuelerNeu { schueler, .. } => schueler.wishes,
+            Aenderung::WuenscheGeaendert { neu, .. } => *neu,
+            _ => None,
+        };
+
+        wishes
+            .map(|wishes| {
+                wishes
+                    .into_iter()
+                    .filter(|p_id| p_id.id() != u32::MAX && !projekte.contains_key(p_id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn wuensche_text(
+    wishes: Option<[ProjektId; 5]>,
+    projekte: &BTreeMap<ProjektId, SaveFileProjekt>,
+) -> String {
+    let Some(wishes) = wishes else {
+        return "---".to_string();
+    };
+
+    wishes
+        .iter()
+        .filter(|p_id| p_id.id() != u32::MAX)
+        .map(|p_id| match projekte.get(p_id) {
+            Some(projekt) => format!("{p_id}: {}", projekt.name),
+            None => format!("{p_id}: unbekannt"),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub fn aenderungen_berechnen(alt: &Data, neu: &Data) -> Vec<Aenderung> {
+    let mut aenderungen = Vec::new();
+
+    for (&projekt_id, projekt) in &neu.projekte {
+        match alt.projekte.get(&projekt_id) {
+            None => aenderungen.push(Aenderung::ProjektNeu {
+                projekt_id,
+                projekt: projekt.clone(),
+            }),
+            Some(alt_projekt) if alt_projekt != projekt => {
+                aenderungen.push(Aenderung::ProjektGeaendert {
+                    projekt_id,
+                    alt: alt_projekt.clone(),
+                    neu: projekt.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (&projekt_id, projekt) in &alt.projekte {
+        if !neu.projekte.contains_key(&projekt_id) {
+            aenderungen.push(Aenderung::ProjektEntfernt {
+                projekt_id,
+                name: projekt.name.clone(),
+            });
+        }
+    }
+
+    for (&schueler_id, schueler) in &neu.schueler {
+        let Some(alt_schueler) = alt.schueler.get(&schueler_id) else {
+            aenderungen.push(Aenderung::SchuelerNeu {
+                schueler_id,
+                schueler: schueler.clone(),
+            });
+            continue;
+        };
+
+        let name = format!("{} ({})", alt_schueler.name, alt_schueler.klasse.klasse());
+
+        if alt_schueler.klasse != schueler.klasse {
+            aenderungen.push(Aenderung::KlasseGeaendert {
+                schueler_id,
+                name: name.clone(),
+                alt: alt_schueler.klasse.clone(),
+                neu: schueler.klasse.clone(),
+            });
+        }
+
+        if alt_schueler.wishes != schueler.wishes {
+            aenderungen.push(Aenderung::WuenscheGeaendert {
+                schueler_id,
+                name,
+                alt: alt_schueler.wishes,
+                neu: schueler.wishes,
+            });
+        }
+
+        let ohne_klasse_und_wuensche = SaveFileSchueler {
+            klasse: alt_schueler.klasse.clone(),
+            wishes: alt_schueler.wishes,
+            ..schueler.clone()
+        };
+
+        if *alt_schueler != ohne_klasse_und_wuensche {
+            aenderungen.push(Aenderung::SchuelerGeaendert {
+                schueler_id,
+                schueler: schueler.clone(),
+            });
+        }
+    }
+
+    for (&schueler_id, schueler) in &alt.schueler {
+        if !neu.schueler.contains_key(&schueler_id) {
+            aenderungen.push(Aenderung::SchuelerEntfernt {
+                schueler_id,
+                name: format!("{} ({})", schueler.name, schueler.klasse.klasse()),
+            });
+        }
+    }
+
+    if alt.zuordnung != neu.zuordnung
+        || alt.feste_zuordnung != neu.feste_zuordnung
+        || alt.klassen != neu.klassen
+        || alt.solver_settings != neu.solver_settings
+        || alt.report != neu.report
+        || alt.snapshots != neu.snapshots
+        || alt.druck != neu.druck
+    {
+        aenderungen.push(Aenderung::Speicherstand);
+    }
+
+    aenderungen
+}
+
+pub fn aenderungen_anwenden<'a>(
+    alt: &Data,
+    importiert: &Data,
+    aenderungen: impl Iterator<Item = &'a Aenderung>,
+) -> Data {
+    let mut data = alt.clone();
+
+    for aenderung in aenderungen {
+        match aenderung {
+            Aenderung::SchuelerNeu {
+                schueler_id,
+                schueler,
+            } => {
+                data.schueler.insert(*schueler_id, schueler.clone());
+            }
+            Aenderung::SchuelerEntfernt { schueler_id, .. } => {
+                data.schueler.remove(schueler_id);
+            }
+            Aenderung::KlasseGeaendert {
+                schueler_id, neu, ..
+            } => {
+                if let Some(schueler) = data.schueler.get_mut(schueler_id) {
+                    schueler.klasse = neu.clone();
+                }
+            }
+            Aenderung::WuenscheGeaendert {
+                schueler_id, neu, ..
+            } => {
+                if let Some(schueler) = data.schueler.get_mut(schueler_id) {
+                    schueler.wishes = *neu;
+                }
+            }
+            Aenderung::SchuelerGeaendert {
+                schueler_id,
+                schueler: neu_schueler,
+            } => {
+                if let Some(schueler) = data.schueler.get_mut(schueler_id) {
+                    *schueler = SaveFileSchueler {
+                        klasse: schueler.klasse.clone(),
+                        wishes: schueler.wishes,
+                        ..neu_schueler.clone()
+                    };
+                }
+            }
+            Aenderung::ProjektNeu {
+                projekt_id,
+                projekt,
+            } => {
+                data.projekte.insert(*projekt_id, projekt.clone());
+            }
+            Aenderung::ProjektEntfernt { projekt_id, .. } => data.projekt_entfernen(projekt_id),
+            Aenderung::ProjektGeaendert {
+                projekt_id, neu, ..
+            } => {
+                data.projekte.insert(*projekt_id, neu.clone());
+            }
+            Aenderung::Speicherstand => {
+                data.zuordnung = importiert.zuordnung.clone();
+                data.feste_zuordnung = importiert.feste_zuordnung.clone();
+                data.klassen = importiert.klassen.clone();
+                data.solver_settings = importiert.solver_settings.clone();
+                data.report = importiert.report.clone();
+                data.snapshots = importiert.snapshots.clone();
+                data.druck = importiert.druck.clone();
+            }
+        }
+    }
+
+    data.bereinigen();
+
+    data
+}
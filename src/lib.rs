@@ -0,0 +1,460 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use gloo::events::EventListener;
+use gloo::utils::document;
+use gloo_console::log;
+use serde::Deserialize;
+use serde::Serialize;
+use web_sys::HtmlElement;
+use web_sys::HtmlInputElement;
+use web_sys::HtmlSelectElement;
+use web_sys::HtmlTextAreaElement;
+use web_sys::KeyboardEvent;
+use web_sys::wasm_bindgen::JsCast;
+use yew::functional::*;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::{ArbeitsbereichAuswahl, VerlaufAuswahl};
+use crate::seiten::Druck;
+use crate::seiten::Einteilung;
+use crate::seiten::Home;
+use crate::seiten::Projekte;
+use crate::seiten::Schueler;
+use crate::types::DruckEinstellungen;
+use crate::types::MerkmalAnteil;
+use crate::types::ProjektId;
+use crate::types::SaveFileKlasse;
+use crate::types::SaveFileProjekt;
+use crate::types::SaveFileSchueler;
+use crate::types::SaveFileStufe;
+use crate::types::SaveFileZuordnung;
+use crate::types::SchuelerId;
+use crate::types::Snapshot;
+use crate::types::SolveReport;
+use crate::types::SolverSettings;
+use crate::types::StufenQuote;
+use crate::verlauf::Verlauf;
+
+pub mod components;
+pub mod druck;
+pub mod export;
+pub mod import;
+pub mod seiten;
+pub mod solver;
+pub mod speicher;
+pub mod types;
+pub mod verlauf;
+
+pub use verlauf::DataContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Routable)]
+enum Route {
+    #[not_found]
+    #[at("/")]
+    Home,
+    #[at("/projekte")]
+    Projekte,
+    #[at("/schueler")]
+    Schueler,
+    #[at("/einteilung")]
+    Einteilung,
+    #[at("/druck")]
+    Druck,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Projekt {
+    name: String,
+    stufen: RangeInclusive<u32>,
+    teilnehmer: RangeInclusive<i32>,
+    num_einteilung: Option<u32>,
+    abgesagt: bool,
+    ignore: bool,
+    quoten: Vec<StufenQuote>,
+    max_pro_klasse: Option<u32>,
+    anteile: Vec<MerkmalAnteil>,
+}
+
+impl Projekt {
+    pub fn get_min_teilnehmer(&self) -> i32 {
+        if self.teilnehmer.start() != &-1 {
+            *self.teilnehmer.start()
+        } else {
+            0
+        }
+    }
+
+    pub fn get_max_teilnehmer(&self) -> i32 {
+        if self.teilnehmer.end() != &-1 {
+            *self.teilnehmer.end()
+        } else {
+            i32::MAX
+        }
+    }
+
+    // Über- (positiv) bzw. Unterschreitung (negativ) der Teilnehmergrenzen nach der Einteilung.
+    // Feste Schüler erhöhen die Höchstzahl wie im Solver.
+    pub fn abweichung(&self, feste_schueler: i32) -> Option<i32> {
+        let num = self.num_einteilung? as i32;
+        let max = self.get_max_teilnehmer().saturating_add(feste_schueler);
+
+        if num > max {
+            Some(num - max)
+        } else if num < self.get_min_teilnehmer() {
+            Some(num - self.get_min_teilnehmer())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Data {
+    pub projekte: BTreeMap<ProjektId, SaveFileProjekt>,
+    pub schueler: BTreeMap<SchuelerId, SaveFileSchueler>,
+    pub zuordnung: Vec<SaveFileZuordnung>,
+    pub klassen: BTreeMap<SaveFileStufe, SaveFileKlasse>,
+    #[serde(default)]
+    pub feste_zuordnung: BTreeMap<SchuelerId, ProjektId>,
+    #[serde(default)]
+    pub solver_settings: SolverSettings,
+    #[serde(default)]
+    pub report: Option<SolveReport>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub druck: DruckEinstellungen,
+}
+
+impl Data {
+    pub fn get_schueler(&self, schueler_id: &SchuelerId) -> Option<&SaveFileSchueler> {
+        self.schueler.get(schueler_id)
+    }
+    pub fn get_projekt(&self, projekt_id: &ProjektId) -> Option<&SaveFileProjekt> {
+        self.projekte.get(projekt_id)
+    }
+
+    pub fn get(&self) -> Data {
+        self.clone()
+    }
+
+    // Angeheftete Schüler und Schüler mit altem "fest"-Flag, die ihrem Erstwunsch zugeordnet sind
+    pub fn feste_schueler(&self, projekt_id: &ProjektId) -> i32 {
+        self.schueler
+            .iter()
+            .filter(|&(s_id, s)| match self.feste_zuordnung.get(s_id) {
+                Some(p_id) => p_id == projekt_id,
+                None => s.fest == Some(true) && s.wishes.is_some_and(|w| w[0] == *projekt_id),
+            })
+            .count() as i32
+    }
+
+    pub fn projekt_entfernen(&mut self, projekt_id: &ProjektId) {
+        self.projekte.remove(projekt_id);
+
+        // Wünsche für das Projekt werden zu "Kein Wunsch"
+        for schueler in self.schueler.values_mut() {
+            if let Some(wishes) = schueler.wishes.as_mut() {
+                wishes
+                    .iter_mut()
+                    .filter(|wish| *wish == projekt_id)
+                    .for_each(|wish| *wish = ProjektId::KEIN_WUNSCH);
+            }
+        }
+
+        self.bereinigen();
+    }
+
+    // Zählt die eingeteilten Schüler je Projekt neu
+    pub fn teilnehmer_zaehlen(&mut self) {
+        for projekt in self.projekte.values_mut() {
+            projekt.num_einteilung = None;
+        }
+
+        for zuordnung in self.zuordnung.iter() {
+            if let Some(projekt) = zuordnung
+                .projekt
+                .and_then(|projekt_id| self.projekte.get_mut(&projekt_id))
+            {
+                projekt.num_einteilung = Some(projekt.num_einteilung.unwrap_or_default() + 1);
+            }
+        }
+    }
+
+    // Entfernt Verweise auf Schüler und Projekte, die es nicht mehr gibt
+    pub fn bereinigen(&mut self) {
+        let schueler = &self.schueler;
+        let projekte = &self.projekte;
+
+        self.zuordnung
+            .retain(|zuordnung| schueler.contains_key(&zuordnung.schueler));
+
+        for zuordnung in self.zuordnung.iter_mut() {
+            if zuordnung
+                .projekt
+                .is_some_and(|p_id| !projekte.contains_key(&p_id))
+            {
+                zuordnung.projekt = None;
+                zuordnung.gesperrt = false;
+            }
+        }
+
+        self.feste_zuordnung
+            .retain(|s_id, p_id| schueler.contains_key(s_id) && projekte.contains_key(p_id));
+
+        let schueler_ids = self.schueler.keys().copied().collect::<Vec<SchuelerId>>();
+        for schueler in self.schueler.values_mut() {
+            if schueler
+                .partner
+                .is_some_and(|partner| !schueler_ids.contains(&partner))
+            {
+                schueler.partner = None;
+            }
+        }
+    }
+}
+
+#[function_component(Secure)]
+fn secure() -> Html {
+    let navigator = use_navigator().unwrap();
+
+    let onclick_callback = Callback::from(move |_| navigator.push(&Route::Home));
+    html! {
+        <div>
+            <h1>{ "Secure" }</h1>
+            <button onclick={onclick_callback}>{ "Go Home" }</button>
+        </div>
+    }
+}
+
+#[function_component(App)]
+pub fn app() -> Html {
+    let route = use_route::<Route>().unwrap_or_default();
+
+    match route {
+        Route::Home => log!("Home"),
+        Route::Projekte => log!("Projekte"),
+        Route::Schueler => log!("Schueler"),
+        Route::Einteilung => log!("Einteilung"),
+        Route::Druck => log!("Druck"),
+    }
+
+    let data = DataContext::new(use_reducer(Verlauf::default));
+    // Solange kein Arbeitsbereich geladen ist, wird nichts gespeichert
+    let arbeitsbereich = use_state(|| None::<String>);
+    let arbeitsbereiche = use_state(Vec::<String>::new);
+    let speicherfehler = use_state(|| None::<String>);
+
+    {
+        let data = data.clone();
+        let arbeitsbereich = arbeitsbereich.clone();
+        let arbeitsbereiche = arbeitsbereiche.clone();
+        let speicherfehler = speicherfehler.clone();
+        use_effect_with((), move |_| {
+            yew::platform::spawn_local(async move {
+                match speicher::starten().await {
+                    Ok((name, geladen, namen)) => {
+                        data.laden(geladen);
+                        arbeitsbereiche.set(namen);
+                        arbeitsbereich.set(Some(name));
+                    }
+                    Err(fehler) => speicherfehler.set(Some(fehler.to_string())),
+                }
+            });
+        })
+    }
+
+    {
+        let speicherfehler = speicherfehler.clone();
+        use_effect_with(
+            (data.clone(), arbeitsbereich.clone()),
+            |(data, arbeitsbereich)| {
+                if let Some(name) = (**arbeitsbereich).clone() {
+                    let data = data.get();
+                    yew::platform::spawn_local(async move {
+                        match speicher::speichern(&name, data).await {
+                            Ok(()) => speicherfehler.set(None),
+                            Err(fehler) => speicherfehler
+                                .set(Some(format!("Speichern fehlgeschlagen: {fehler}"))),
+                        }
+                    });
+                }
+            },
+        )
+    }
+
+    {
+        let data = data.clone();
+        use_effect_with((), move |_| {
+            let listener = EventListener::new(&document(), "keydown", move |event| {
+                let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                if !event.ctrl_key() && !event.meta_key() {
+                    return;
+                }
+
+                // In Eingabefeldern bleibt das Rückgängigmachen des Browsers erhalten
+                let eingabefeld = event.target().is_some_and(|target| {
+                    target
+                        .dyn_ref::<HtmlInputElement>()
+                        .is_some_and(|input| input.type_() != "checkbox")
+                        || target.has_type::<HtmlTextAreaElement>()
+                        || target.has_type::<HtmlSelectElement>()
+                        || target
+                            .dyn_ref::<HtmlElement>()
+                            .is_some_and(HtmlElement::is_content_editable)
+                });
+                if eingabefeld {
+                    return;
+                }
+
+                match event.key().to_lowercase().as_str() {
+                    "z" if event.shift_key() => data.wiederholen(1),
+                    "z" => data.rueckgaengig(1),
+                    "y" => data.wiederholen(1),
+                    _ => return,
+                }
+
+                event.prevent_default();
+            });
+
+            move || drop(listener)
+        })
+    }
+
+    let wechseln = {
+        let data = data.clone();
+        let arbeitsbereich = arbeitsbereich.clone();
+        let speicherfehler = speicherfehler.clone();
+        Callback::from(move |name: String| {
+            let data = data.clone();
+            let arbeitsbereich = arbeitsbereich.clone();
+            let speicherfehler = speicherfehler.clone();
+
+            arbeitsbereich.set(None);
+            yew::platform::spawn_local(async move {
+                match speicher::laden(&name).await {
+                    Ok(geladen) => {
+                        data.laden(geladen.unwrap_or_default());
+                        speicher::aktiven_arbeitsbereich_setzen(&name);
+                        arbeitsbereich.set(Some(name));
+                    }
+                    Err(fehler) => speicherfehler.set(Some(fehler.to_string())),
+                }
+            });
+        })
+    };
+
+    let neu = {
+        let data = data.clone();
+        let arbeitsbereich = arbeitsbereich.clone();
+        let arbeitsbereiche = arbeitsbereiche.clone();
+        let wechseln = wechseln.clone();
+        Callback::from(move |name: String| {
+            if arbeitsbereiche.contains(&name) {
+                wechseln.emit(name);
+                return;
+            }
+
+            let mut namen = (*arbeitsbereiche).clone();
+            namen.push(name.clone());
+            arbeitsbereiche.set(namen);
+
+            data.laden(Data::default());
+            speicher::aktiven_arbeitsbereich_setzen(&name);
+            arbeitsbereich.set(Some(name));
+        })
+    };
+
+    let loeschen = {
+        let data = data.clone();
+        let arbeitsbereich = arbeitsbereich.clone();
+        let arbeitsbereiche = arbeitsbereiche.clone();
+        let speicherfehler = speicherfehler.clone();
+        let wechseln = wechseln.clone();
+        Callback::from(move |name: String| {
+            let data = data.clone();
+            let arbeitsbereich = arbeitsbereich.clone();
+            let arbeitsbereiche = arbeitsbereiche.clone();
+            let speicherfehler = speicherfehler.clone();
+            let wechseln = wechseln.clone();
+
+            arbeitsbereich.set(None);
+            yew::platform::spawn_local(async move {
+                if let Err(fehler) = speicher::loeschen(&name).await {
+                    speicherfehler.set(Some(format!("Löschen fehlgeschlagen: {fehler}")));
+                    arbeitsbereich.set(Some(name));
+                    return;
+                }
+
+                let namen = (*arbeitsbereiche)
+                    .iter()
+                    .filter(|n| **n != name)
+                    .cloned()
+                    .collect::<Vec<String>>();
+
+                // Es bleibt immer mindestens ein Arbeitsbereich übrig
+                match namen.first().cloned() {
+                    Some(naechster) => {
+                        arbeitsbereiche.set(namen);
+                        wechseln.emit(naechster);
+                    }
+                    None => {
+                        let name = speicher::STANDARD_ARBEITSBEREICH.to_string();
+                        data.laden(Data::default());
+                        speicher::aktiven_arbeitsbereich_setzen(&name);
+                        arbeitsbereiche.set(vec![name.clone()]);
+                        arbeitsbereich.set(Some(name));
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+    <ContextProvider<DataContext> context={data}>
+        <HashRouter>
+            <nav>
+                <Link<Route> to={Route::Home} classes={if route == Route::Home {"current"} else { "" }}>{ "Home" }</Link<Route>>
+                <Link<Route> to={Route::Projekte} classes={if route == Route::Projekte {"current"} else { "" }}>{ "Projekte" }</Link<Route>>
+                <Link<Route> to={Route::Schueler} classes={if route == Route::Schueler {"current"} else { "" }}>{ "Schueler" }</Link<Route>>
+                <Link<Route> to={Route::Einteilung} classes={if route == Route::Einteilung {"current"} else { "" }}>{ "Einteilung" }</Link<Route>>
+                <Link<Route> to={Route::Druck} classes={if route == Route::Druck {"current"} else { "" }}>{ "Drucken" }</Link<Route>>
+                <VerlaufAuswahl />
+                <ArbeitsbereichAuswahl
+                    aktiv={ (*arbeitsbereich).clone() }
+                    arbeitsbereiche={ (*arbeitsbereiche).clone() }
+                    onwechseln={ wechseln }
+                    onneu={ neu }
+                    onloeschen={ loeschen }
+                />
+            </nav>
+            if let Some(fehler) = (*speicherfehler).clone() {
+                <p class="speicherfehler">{ fehler }</p>
+            }
+            <Switch<Route> render={switch} />
+        </HashRouter>
+    </ContextProvider<DataContext>>
+    }
+}
+
+fn switch(routes: Route) -> Html {
+    match routes {
+        Route::Home => html! { <Home /> },
+        Route::Projekte => html! {
+            <Projekte />
+        },
+        Route::Schueler => html! {
+            <Schueler />
+        },
+        Route::Einteilung => html! {
+            <Einteilung />
+        },
+        Route::Druck => html! {
+            <Druck />
+        },
+    }
+}
@@ -0,0 +1,259 @@
+use gloo::utils::window;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::{Component, Context, ContextHandle, Html, TargetCast, html, html::onchange};
+
+use crate::{
+    DataContext,
+    druck::{
+        Einteilung, Klassenliste, PLATZHALTER, Projektliste, klassenlisten, projektlisten, zettel,
+    },
+};
+
+pub enum Msg {
+    DataUpdate(DataContext),
+    Projekttage(u32),
+    Vorlage(String),
+    Art(DruckArt),
+    Drucken,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DruckArt {
+    Projektlisten,
+    Klassenlisten,
+    Zettel,
+}
+
+pub struct Druck {
+    data: DataContext,
+    art: DruckArt,
+    _context_listener: ContextHandle<DataContext>,
+}
+
+impl Component for Druck {
+    type Message = Msg;
+
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (data, context_listener) = ctx
+            .link()
+            .context::<DataContext>(ctx.link().callback(Msg::DataUpdate))
+            .expect("Kein Datenkontext");
+
+        Self {
+            data,
+            art: DruckArt::Projektlisten,
+            _context_listener: context_listener,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let projekttage = self.data.druck.projekttage;
+
+        html! {
+            <div class="seite druck">
+                <div class="einstellungen keine_druck">
+                    <label>
+                        { "Listen" }
+                        <select onchange={ ctx.link().callback(|event: onchange::Event| {
+                            let select = event.target_unchecked_into::<HtmlInputElement>();
+                            Msg::Art(match select.value().as_str() {
+                                "klassen" => DruckArt::Klassenlisten,
+                                "zettel" => DruckArt::Zettel,
+                                _ => DruckArt::Projektlisten,
+                            })
+                        }) }>
+                            <option value="projekte" selected={ self.art == DruckArt::Projektlisten }>{ "Teilnehmerlisten je Projekt" }</option>
+                            <option value="klassen" selected={ self.art == DruckArt::Klassenlisten }>{ "Listen je Klasse" }</option>
+                            <option value="zettel" selected={ self.art == DruckArt::Zettel }>{ "Benachrichtigungszettel" }</option>
+                        </select>
+                    </label>
+                    if self.art == DruckArt::Projektlisten {
+                    <label>
+                        { "Projekttage (Anwesenheitsspalten)" }
+                        <input type="number" min="1" step="1"
+                            value={ projekttage.to_string() }
+                            onchange={ ctx.link().batch_callback(|event: onchange::Event| {
+                                let input = event.target_unchecked_into::<HtmlInputElement>();
+                                str::parse::<u32>(&input.value()).ok().filter(|&tage| tage > 0).map(Msg::Projekttage)
+                            }) } />
+                    </label>
+                    }
+                </div>
+                if self.art == DruckArt::Zettel {
+                    <div class="vorlage keine_druck">
+                        <label for="vorlage">{ "Vorlage" }</label>
+                        <textarea id="vorlage" rows="5" value={ self.data.druck.vorlage.clone() }
+                            onchange={ ctx.link().callback(|event: onchange::Event| {
+                                let textarea = event.target_unchecked_into::<HtmlTextAreaElement>();
+                                Msg::Vorlage(textarea.value())
+                            }) } />
+                        <ul>
+                            { for PLATZHALTER.iter().map(|(platzhalter, beschreibung)| html! {
+                                <li><code>{ *platzhalter }</code>{ format!(": {beschreibung}") }</li>
+                            }) }
+                        </ul>
+                    </div>
+                }
+                <button class="keine_druck" onclick={ctx.link().callback(|_| Msg::Drucken)}>{"Drucken"}</button>
+                if self.data.zuordnung.is_empty() {
+                    <p class="keine_druck">{ "Es gibt noch keine Einteilung" }</p>
+                }
+                { match self.art {
+                    DruckArt::Projektlisten => html! {
+                        <>{ for projektlisten(&self.data).iter().map(|liste| projektliste(liste, projekttage)) }</>
+                    },
+                    DruckArt::Klassenlisten => html! {
+                        <>{ for klassenlisten(&self.data).iter().map(klassenliste) }</>
+                    },
+                    DruckArt::Zettel => html! {
+                        <div class="zettel">
+                            { for zettel(&self.data).into_iter().map(|text| html! {
+                                <div class="zettel_eintrag">{ text }</div>
+                            }) }
+                        </div>
+                    },
+                } }
+            </div>
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DataUpdate(data) => {
+                self.data = data;
+
+                true
+            }
+            Msg::Projekttage(projekttage) => {
+                let mut data = self.data.get();
+                data.druck.projekttage = projekttage;
+
+                self.data.aendern(
+                    data,
+                    format!("Anzahl der Projekttage auf {projekttage} gesetzt"),
+                );
+
+                false
+            }
+            Msg::Vorlage(vorlage) => {
+                let mut data = self.data.get();
+                data.druck.vorlage = vorlage;
+
+                self.data.aendern(
+                    data,
+                    "Vorlage für Benachrichtigungszettel geändert".to_string(),
+                );
+
+                false
+            }
+            Msg::Art(art) => {
+                self.art = art;
+
+                true
+            }
+            Msg::Drucken => {
+                let _ = window().print();
+
+                false
+            }
+        }
+    }
+}
+
+fn projektliste(liste: &Projektliste, projekttage: u32) -> Html {
+    html! {
+        <section class="druckseite">
+            <h1>{ format!("{}: {}", liste.projekt_id, liste.name) }</h1>
+            <p class="kopf">
+                { format!("Stufe {}-{}", liste.min_stufe, liste.max_stufe) }
+                if !liste.raum.is_empty() {
+                    { format!(", Raum {}", liste.raum) }
+                }
+                if !liste.betreuer.is_empty() {
+                    { format!(", Betreuer: {}", liste.betreuer) }
+                }
+                { format!(", {} Teilnehmer", liste.teilnehmer.len()) }
+            </p>
+            <table class="teilnehmerliste">
+                <thead>
+                    <tr>
+                        <th>{ "Name" }</th>
+                        <th>{ "Klasse" }</th>
+                        { for (1..=projekttage).map(|tag| html! { <th class="anwesenheit">{ format!("Tag {tag}") }</th> }) }
+                    </tr>
+                </thead>
+                <tbody>
+                    { for liste.teilnehmer.iter().map(|teilnehmer| html! {
+                        <tr>
+                            <td>{ teilnehmer.name.clone() }</td>
+                            <td>{ teilnehmer.klasse.clone() }</td>
+                            { for (1..=projekttage).map(|_| html! { <td class="anwesenheit"></td> }) }
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        </section>
+    }
+}
+
+fn klassenliste(liste: &Klassenliste) -> Html {
+    let nicht_eingeteilt = liste
+        .schueler
+        .iter()
+        .filter(|eintrag| eintrag.einteilung == Einteilung::NichtEingeteilt)
+        .count();
+    let ignoriert = liste
+        .schueler
+        .iter()
+        .filter(|eintrag| eintrag.einteilung == Einteilung::Ignoriert)
+        .count();
+
+    html! {
+        <section class="druckseite">
+            <h1>{ format!("Klasse {}", liste.klasse) }</h1>
+            <p class="kopf">
+                { format!("{} Schüler", liste.schueler.len()) }
+                if nicht_eingeteilt > 0 {
+                    { format!(", {nicht_eingeteilt} nicht eingeteilt") }
+                }
+                if ignoriert > 0 {
+                    { format!(", {ignoriert} ignoriert") }
+                }
+            </p>
+            <table class="teilnehmerliste">
+                <thead>
+                    <tr>
+                        <th>{ "Name" }</th>
+                        <th>{ "Projekt" }</th>
+                        <th>{ "Raum" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for liste.schueler.iter().map(|eintrag| match &eintrag.einteilung {
+                        Einteilung::Projekt { name, raum } => html! {
+                            <tr>
+                                <td>{ eintrag.name.clone() }</td>
+                                <td>{ name.clone() }</td>
+                                <td>{ raum.clone() }</td>
+                            </tr>
+                        },
+                        Einteilung::NichtEingeteilt => html! {
+                            <tr class="nicht_eingeteilt">
+                                <td>{ eintrag.name.clone() }</td>
+                                <td colspan="2">{ "Nicht eingeteilt" }</td>
+                            </tr>
+                        },
+                        Einteilung::Ignoriert => html! {
+                            <tr class="ignoriert">
+                                <td>{ eintrag.name.clone() }</td>
+                                <td colspan="2">{ "Ignoriert (nimmt nicht teil)" }</td>
+                            </tr>
+                        },
+                    }) }
+                </tbody>
+            </table>
+        </section>
+    }
+}
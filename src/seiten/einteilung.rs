@@ -1,34 +1,75 @@
 use std::collections::{BTreeMap, HashMap};
 
+use gloo::timers::callback::Interval;
 use gloo_console::log;
 use serde::Serialize;
 use web_sys::{HtmlInputElement, wasm_bindgen::JsCast};
 use yew::{
-    Callback, Component, Context, ContextHandle, ContextProvider, Html, Properties,
-    function_component, html, html::onchange, platform::spawn_local, use_context,
+    Callback, Component, Context, ContextHandle, ContextProvider, Html, Properties, TargetCast,
+    function_component, html, html::onchange, use_context,
 };
 use yew_custom_components::table::types::{ColumnBuilder, TableData};
 
 use crate::{
     Data, DataContext, Projekt,
-    components::Tabelle,
-    solver::solve_good_lp,
-    types::{Klasse, ProjektId, SaveFileZuordnung, SchuelerId},
+    components::{SnapshotListe, Statistik, Tabelle},
+    solver::{
+        SolverResult,
+        diagnose::Konflikt,
+        worker::{SolverAnfrage, SolverAntwort, SolverVerbindung},
+    },
+    types::{Klasse, ProjektId, SaveFileZuordnung, SchuelerId, Snapshot, SolveReport},
 };
 
 pub enum Msg {
     DataUpdate(DataContext),
-    DataSet(Data),
+    DataSet(Data, String),
     SolveButton,
     Solve(Data),
     Edit(SchuelerId, Edit),
+    Einstellung(Einstellung),
+    SperrenAufheben,
+    SnapshotSichern(String),
+    SnapshotWiederherstellen(usize),
+    SnapshotUmbenennen(usize, String),
+    SnapshotLoeschen(usize),
+    SolverAntwort(SolverAntwort),
+    Tick,
+    Abbrechen,
 }
 
+pub enum Einstellung {
+    Gewichtung { idx: u8, value: f64 },
+    PartnerBonus { value: f64 },
+    NichtGewuenscht { value: f64 },
+    ManuelleBehalten { value: bool },
+    WeicheGrenzen { value: bool },
+    Schlupfstrafe { value: f64 },
+    ProjekteAbsagen { value: bool },
+    MaxAbsagen { value: Option<u32> },
+    WeicheAnteile { value: bool },
+    Anteilstrafe { value: f64 },
+}
+
+// Anzahl der automatischen Snapshots ("Lauf N"), die aufgehoben werden
+const MAX_LAEUFE: usize = 10;
+
 pub struct Einteilung {
     data: DataContext,
     onchange: Callback<(SchuelerId, Edit)>,
     _context_listener: ContextHandle<DataContext>,
     verteilung: HashMap<SchuelerId, Option<ProjektId>>,
+    konflikte: Vec<Konflikt>,
+    solver: Option<LaufenderSolver>,
+}
+
+struct LaufenderSolver {
+    // Beim Verwerfen wird der Worker beendet
+    _verbindung: SolverVerbindung,
+    _interval: Interval,
+    data: Data,
+    schritt: String,
+    sekunden: u32,
 }
 
 fn get_verteilung(data: &Data) -> HashMap<SchuelerId, Option<ProjektId>> {
@@ -49,14 +90,14 @@ impl Component for Einteilung {
             .context::<DataContext>(ctx.link().callback(Msg::DataUpdate))
             .expect("Kein Datenkontext");
 
-        log!("CREATE");
-
         if data.zuordnung.is_empty() {
             ctx.link().send_message(Msg::Solve(data.get()));
         }
 
         Self {
             verteilung: get_verteilung(&data),
+            konflikte: Vec::new(),
+            solver: None,
             onchange: ctx
                 .link()
                 .callback(|(schueler_id, edit)| Msg::Edit(schueler_id, edit)),
@@ -98,6 +139,18 @@ impl Component for Einteilung {
                 .data_property("wuensche")
                 .header_class("user-select-none")
                 .build(),
+            ColumnBuilder::new("fest")
+                .orderable(true)
+                .short_name("Angeheftet")
+                .data_property("fest")
+                .header_class("user-select-none")
+                .build(),
+            ColumnBuilder::new("gesperrt")
+                .orderable(true)
+                .short_name("Manuell geändert")
+                .data_property("gesperrt")
+                .header_class("user-select-none")
+                .build(),
         ];
 
         let mut table_data = Vec::new();
@@ -110,10 +163,155 @@ impl Component for Einteilung {
             ));
         }
 
+        let settings = &self.data.solver_settings;
+
+        let abweichungen = self
+            .data
+            .projekte
+            .iter()
+            .filter_map(|(projekt_id, projekt)| {
+                Projekt::from(projekt.clone())
+                    .abweichung(self.data.feste_schueler(projekt_id))
+                    .map(|abweichung| (projekt.name.clone(), abweichung))
+            })
+            .collect::<Vec<(String, i32)>>();
+
+        // Schüler, deren Wünsche auf ignorierte Projekte fallen
+        let verlorene_wuensche = self
+            .data
+            .schueler
+            .values()
+            .filter(|schueler| !schueler.ignore)
+            .filter_map(|schueler| {
+                let projekte = schueler
+                    .wishes?
+                    .iter()
+                    .filter_map(|p_id| self.data.get_projekt(p_id))
+                    .filter(|projekt| projekt.ignore)
+                    .map(|projekt| projekt.name.clone())
+                    .collect::<Vec<String>>();
+
+                (!projekte.is_empty()).then(|| {
+                    (
+                        format!("{} ({})", schueler.name, schueler.klasse.klasse()),
+                        projekte,
+                    )
+                })
+            })
+            .collect::<Vec<(String, Vec<String>)>>();
+
         html! {
             <ContextProvider<Callback<(SchuelerId,Edit)>> context={ self.onchange.clone() }>
                 <div class="seite">
-                    <button onclick={ctx.link().callback(move |_| Msg::SolveButton)}>{"Lösen"}</button>
+                    <div class="einstellungen">
+                        { for settings.weights.iter().enumerate().map(|(idx, &gewicht)| html! {
+                            <label>
+                                { format!("Gewichtung {}. Wunsch", idx + 1) }
+                                { zahl_input(ctx, gewicht, move |value| Einstellung::Gewichtung { idx: idx as u8, value }) }
+                            </label>
+                        })}
+                        <label>
+                            { "Bonus für Wunschpartner" }
+                            { zahl_input(ctx, settings.partner_weight, |value| Einstellung::PartnerBonus { value }) }
+                        </label>
+                        <label>
+                            { "Strafe für nicht gewünschte Projekte" }
+                            { zahl_input(ctx, settings.not_wished_penalty, |value| Einstellung::NichtGewuenscht { value }) }
+                        </label>
+                        <label>
+                            { "Manuelle Änderungen beim Lösen behalten" }
+                            { checkbox_input(ctx, settings.keep_manual, |value| Einstellung::ManuelleBehalten { value }) }
+                        </label>
+                        <label>
+                            { "Weiche Teilnehmergrenzen" }
+                            { checkbox_input(ctx, settings.soft_constraints, |value| Einstellung::WeicheGrenzen { value }) }
+                        </label>
+                        if settings.soft_constraints {
+                            <label>
+                                { "Strafe je Schüler über/unter der Grenze" }
+                                { zahl_input(ctx, settings.slack_penalty, |value| Einstellung::Schlupfstrafe { value }) }
+                            </label>
+                        }
+                        <label>
+                            { "Weiche Anteile je Merkmal" }
+                            { checkbox_input(ctx, settings.soft_shares, |value| Einstellung::WeicheAnteile { value }) }
+                        </label>
+                        if settings.soft_shares {
+                            <label>
+                                { "Strafe je Schüler über/unter einem Anteil" }
+                                { zahl_input(ctx, settings.share_penalty, |value| Einstellung::Anteilstrafe { value }) }
+                            </label>
+                        }
+                        <label>
+                            { "Unterbelegte Projekte absagen" }
+                            { checkbox_input(ctx, settings.cancel_projects, |value| Einstellung::ProjekteAbsagen { value }) }
+                        </label>
+                        if settings.cancel_projects {
+                            <label>
+                                { "Höchstens abgesagte Projekte (leer = beliebig)" }
+                                <input type="number" min="0" step="1"
+                                    value={ settings.max_cancelled.map(|max| max.to_string()).unwrap_or_default() }
+                                    onchange={ ctx.link().callback(|event: onchange::Event| {
+                                        let input = event.target_unchecked_into::<HtmlInputElement>();
+                                        Msg::Einstellung(Einstellung::MaxAbsagen { value: str::parse::<u32>(&input.value()).ok() })
+                                    }) } />
+                            </label>
+                        }
+                    </div>
+                    <button onclick={ctx.link().callback(move |_| Msg::SolveButton)} disabled={ self.solver.is_some() }>{"Lösen"}</button>
+                    <button onclick={ctx.link().callback(move |_| Msg::SperrenAufheben)}>{"Alle Sperren aufheben"}</button>
+                    if let Some(solver) = &self.solver {
+                        <div class="fortschritt">
+                            <progress />
+                            <span>{ format!("{} ({} s)", solver.schritt, solver.sekunden) }</span>
+                            <button onclick={ctx.link().callback(move |_| Msg::Abbrechen)}>{"Abbrechen"}</button>
+                        </div>
+                    }
+                    if !self.konflikte.is_empty() {
+                        <div class="konflikte">
+                            <h2>{ "Keine Einteilung möglich" }</h2>
+                            <ul>
+                                { for self.konflikte.iter().map(|konflikt| html! (<li>{ konflikt.to_string() }</li>)) }
+                            </ul>
+                        </div>
+                    }
+                    if !abweichungen.is_empty() {
+                        <div class="abweichungen">
+                            <h2>{ "Teilnehmergrenzen nicht eingehalten" }</h2>
+                            <ul>
+                                { for abweichungen.iter().map(|(name, abweichung)| html! {
+                                    <li>
+                                        if *abweichung > 0 {
+                                            { format!("{name}: {abweichung} Schüler über dem Maximum") }
+                                        } else {
+                                            { format!("{name}: {} Schüler unter dem Minimum", -abweichung) }
+                                        }
+                                    </li>
+                                }) }
+                            </ul>
+                        </div>
+                    }
+                    if !verlorene_wuensche.is_empty() {
+                        <details class="verlorene_wuensche">
+                            <summary>{ format!("{} Schüler verlieren Wünsche an ignorierte Projekte", verlorene_wuensche.len()) }</summary>
+                            <ul>
+                                { for verlorene_wuensche.iter().map(|(schueler, projekte)| html! {
+                                    <li>{ format!("{schueler}: {}", projekte.join(", ")) }</li>
+                                }) }
+                            </ul>
+                        </details>
+                    }
+                    if let Some(report) = &self.data.report {
+                        <Statistik report={ report.clone() } />
+                    }
+                    <SnapshotListe
+                        snapshots={ self.data.snapshots.clone() }
+                        aktuell={ Snapshot::new("Aktuelle Einteilung".to_string(), &self.data) }
+                        onsichern={ ctx.link().callback(Msg::SnapshotSichern) }
+                        onwiederherstellen={ ctx.link().callback(Msg::SnapshotWiederherstellen) }
+                        onumbenennen={ ctx.link().callback(|(idx, name)| Msg::SnapshotUmbenennen(idx, name)) }
+                        onloeschen={ ctx.link().callback(Msg::SnapshotLoeschen) }
+                    />
                     <Tabelle<EinteilungTableLine> columns={columns} table_data={table_data} />
                 </div>
             </ContextProvider<Callback<(SchuelerId,Edit)>>>
@@ -124,13 +322,12 @@ impl Component for Einteilung {
         match msg {
             Msg::DataUpdate(data) => {
                 self.data = data;
-                let _ = self.data.save();
+                self.verteilung = get_verteilung(&self.data);
 
                 true
             }
-            Msg::DataSet(data) => {
-                self.data.set(data);
-                let _ = self.data.save();
+            Msg::DataSet(data, beschreibung) => {
+                self.data.aendern(data, beschreibung);
 
                 true
             }
@@ -140,79 +337,89 @@ impl Component for Einteilung {
             }
             Msg::Solve(data) => {
                 if data.zuordnung == self.data.zuordnung {
-                    log!("Solve111!");
-
-                    let link = ctx.link().clone();
-
-                    let data2 = data.clone();
-
-                    let callback = Callback::from(move |result: Option<Vec<SaveFileZuordnung>>| {
-                        if let Some(result) = result {
-                            let mut data = data2.clone();
-
-                            data.projekte
-                                .iter_mut()
-                                .for_each(|(_p_id, p)| p.num_einteilung = None);
-
-                            for zuordnung in result.iter() {
-                                if let Some(projekt_id) = zuordnung.projekt {
-                                    let projekt = data.projekte.get_mut(&projekt_id);
-
-                                    if let Some(projekt) = projekt {
-                                        if let Some(num) = projekt.num_einteilung {
-                                            projekt.num_einteilung = Some(num + 1);
-                                        } else {
-                                            projekt.num_einteilung = Some(1)
-                                        }
-                                    }
-                                }
-                            }
-
-                            data.zuordnung = result;
+                    if self.solver.is_some() {
+                        return false;
+                    }
 
-                            link.send_message(Msg::DataSet(data));
+                    let verbindung = {
+                        let link = ctx.link().clone();
+
+                        SolverVerbindung::starten(
+                            "solver_worker.js",
+                            &solver_anfrage(&data),
+                            move |antwort| link.send_message(Msg::SolverAntwort(antwort)),
+                        )
+                    };
+
+                    let verbindung = match verbindung {
+                        Ok(verbindung) => verbindung,
+                        Err(fehler) => {
+                            log!("Solver konnte nicht gestartet werden", fehler.clone());
+                            self.konflikte = vec![Konflikt::Unbekannt(format!(
+                                "Solver konnte nicht gestartet werden: {fehler:?}"
+                            ))];
+                            return true;
                         }
-                    });
-
-                    spawn_local(async move {
-                        let data = data.clone();
+                    };
 
-                        let a = solve_task(data);
+                    let interval = {
+                        let link = ctx.link().clone();
 
-                        let result = a.await;
+                        Interval::new(1000, move || link.send_message(Msg::Tick))
+                    };
 
-                        callback.emit(result);
+                    self.solver = Some(LaufenderSolver {
+                        _verbindung: verbindung,
+                        _interval: interval,
+                        data,
+                        schritt: "Solver starten".to_string(),
+                        sekunden: 0,
                     });
 
-                    // let result = self.solve(&data);
-
-                    // if let Some(verteilung) = result {
-                    //     let mut data = self.data.get();
+                    true
+                } else {
+                    self.verteilung = get_verteilung(&data);
+                    true
+                }
+            }
+            Msg::SolverAntwort(SolverAntwort::Fortschritt(schritt)) => {
+                if let Some(solver) = self.solver.as_mut() {
+                    solver.schritt = schritt;
+                }
 
-                    //     data.zuordnung = verteilung
-                    //         .iter()
-                    //         .enumerate()
-                    //         .map(|(idx, (schueler_id, projekt_id))| SaveFileZuordnung {
-                    //             id: idx as u32,
-                    //             schueler: *schueler_id,
-                    //             projekt: *projekt_id,
-                    //         })
-                    //         .collect::<Vec<SaveFileZuordnung>>();
+                true
+            }
+            Msg::SolverAntwort(SolverAntwort::Ergebnis(ergebnis)) => {
+                let Some(solver) = self.solver.take() else {
+                    return false;
+                };
 
-                    //     ctx.link().send_message(Msg::DataSet(data));
+                match ergebnis {
+                    Ok(result) => {
+                        self.konflikte = Vec::new();
+                        ctx.link().send_message(Msg::DataSet(
+                            solver_ergebnis(&self.data, &solver.data, result),
+                            "Einteilung berechnet".to_string(),
+                        ));
+                    }
+                    Err(konflikte) => self.konflikte = konflikte,
+                }
 
-                    //     self.verteilung = verteilung;
+                true
+            }
+            Msg::Tick => {
+                if let Some(solver) = self.solver.as_mut() {
+                    solver.sekunden += 1;
+                }
 
-                    //     true
-                    // } else {
-                    //     false
-                    // }
+                true
+            }
+            Msg::Abbrechen => {
+                log!("Solver abgebrochen");
+                // Beendet den Worker, ein neuer Lauf startet in einem frischen Worker
+                self.solver = None;
 
-                    false
-                } else {
-                    self.verteilung = get_verteilung(&data);
-                    true
-                }
+                true
             }
             Msg::Edit(schueler_id, edit) => {
                 log!("Edit");
@@ -221,82 +428,341 @@ impl Component for Einteilung {
 
                 let zuteilung = data.zuordnung.iter().find(|z| z.schueler == schueler_id);
 
-                if let Some(_zuteilung) = zuteilung {
+                if let Some(zuteilung) = zuteilung {
+                    let aktuelles_projekt = zuteilung.projekt;
                     let mut zuordnungen = data.zuordnung;
 
+                    let name = data
+                        .schueler
+                        .get(&schueler_id)
+                        .map(|s| s.name.clone())
+                        .unwrap_or_default();
+                    let beschreibung = match &edit {
+                        Edit::Projekt { projekt_id } => {
+                            format!("{name} in Projekt {projekt_id} eingeteilt")
+                        }
+                        Edit::Entsperren => format!("Zuordnung von {name} entsperrt"),
+                        Edit::Fest { value: true } => format!("{name} fest zugeordnet"),
+                        Edit::Fest { value: false } => {
+                            format!("Feste Zuordnung von {name} aufgehoben")
+                        }
+                    };
+
                     match edit {
-                        Edit::Projekt { projekt_id } => zuordnungen.iter_mut().for_each(|z| {
+                        Edit::Projekt { projekt_id } => {
+                            zuordnungen.iter_mut().for_each(|z| {
+                                if z.schueler == schueler_id {
+                                    z.projekt = Some(projekt_id);
+                                    z.gesperrt = true;
+                                }
+                            });
+
+                            if data.feste_zuordnung.contains_key(&schueler_id) {
+                                data.feste_zuordnung.insert(schueler_id, projekt_id);
+                            }
+                        }
+                        Edit::Entsperren => zuordnungen.iter_mut().for_each(|z| {
                             if z.schueler == schueler_id {
-                                z.projekt = Some(projekt_id)
+                                z.gesperrt = false
                             }
                         }),
+                        Edit::Fest { value } => {
+                            if value {
+                                if let Some(projekt_id) = aktuelles_projekt {
+                                    data.feste_zuordnung.insert(schueler_id, projekt_id);
+                                }
+                            } else {
+                                data.feste_zuordnung.remove(&schueler_id);
+                            }
+                        }
                     }
 
                     data.zuordnung = zuordnungen;
+                    data.teilnehmer_zaehlen();
+                    data.report = Some(report_aus_zuordnung(&data));
 
-                    ctx.link().send_message(Msg::DataSet(data));
+                    ctx.link().send_message(Msg::DataSet(data, beschreibung));
                 }
 
                 true
             }
+            Msg::Einstellung(einstellung) => {
+                let mut data = self.data.get();
+
+                match einstellung {
+                    Einstellung::Gewichtung { idx, value } => {
+                        data.solver_settings.weights[idx as usize] = value
+                    }
+                    Einstellung::PartnerBonus { value } => {
+                        data.solver_settings.partner_weight = value
+                    }
+                    Einstellung::NichtGewuenscht { value } => {
+                        data.solver_settings.not_wished_penalty = value
+                    }
+                    Einstellung::ManuelleBehalten { value } => {
+                        data.solver_settings.keep_manual = value
+                    }
+                    Einstellung::WeicheGrenzen { value } => {
+                        data.solver_settings.soft_constraints = value
+                    }
+                    Einstellung::Schlupfstrafe { value } => {
+                        data.solver_settings.slack_penalty = value
+                    }
+                    Einstellung::ProjekteAbsagen { value } => {
+                        data.solver_settings.cancel_projects = value
+                    }
+                    Einstellung::MaxAbsagen { value } => data.solver_settings.max_cancelled = value,
+                    Einstellung::WeicheAnteile { value } => {
+                        data.solver_settings.soft_shares = value
+                    }
+                    Einstellung::Anteilstrafe { value } => {
+                        data.solver_settings.share_penalty = value
+                    }
+                }
+
+                ctx.link().send_message(Msg::DataSet(
+                    data,
+                    "Solver-Einstellungen geändert".to_string(),
+                ));
+
+                false
+            }
+            Msg::SperrenAufheben => {
+                let mut data = self.data.get();
+
+                data.zuordnung.iter_mut().for_each(|z| z.gesperrt = false);
+
+                ctx.link()
+                    .send_message(Msg::DataSet(data, "Alle Sperren aufgehoben".to_string()));
+
+                false
+            }
+            Msg::SnapshotSichern(name) => {
+                let mut data = self.data.get();
+
+                let beschreibung = format!("Snapshot \"{name}\" gesichert");
+                let snapshot = Snapshot::new(name, &data);
+                data.snapshots.push(snapshot);
+
+                ctx.link().send_message(Msg::DataSet(data, beschreibung));
+
+                false
+            }
+            Msg::SnapshotWiederherstellen(idx) => {
+                let mut data = self.data.get();
+
+                let Some(snapshot) = data.snapshots.get(idx).cloned() else {
+                    return false;
+                };
+
+                snapshot.wiederherstellen(&mut data);
+
+                ctx.link().send_message(Msg::DataSet(
+                    data,
+                    format!("Snapshot \"{}\" wiederhergestellt", snapshot.name),
+                ));
+
+                false
+            }
+            Msg::SnapshotUmbenennen(idx, name) => {
+                let mut data = self.data.get();
+
+                let Some(snapshot) = data.snapshots.get_mut(idx) else {
+                    return false;
+                };
+
+                let beschreibung =
+                    format!("Snapshot \"{}\" in \"{name}\" umbenannt", snapshot.name);
+                snapshot.name = name;
+                // Umbenannte Läufe werden nicht mehr automatisch entfernt
+                snapshot.lauf = None;
+
+                ctx.link().send_message(Msg::DataSet(data, beschreibung));
+
+                false
+            }
+            Msg::SnapshotLoeschen(idx) => {
+                let mut data = self.data.get();
+
+                if idx >= data.snapshots.len() {
+                    return false;
+                }
+
+                let snapshot = data.snapshots.remove(idx);
+
+                ctx.link().send_message(Msg::DataSet(
+                    data,
+                    format!("Snapshot \"{}\" gelöscht", snapshot.name),
+                ));
+
+                false
+            }
         }
     }
 }
 
-pub async fn solve_task(data: Data) -> Option<Vec<SaveFileZuordnung>> {
+fn zahl_input(
+    ctx: &Context<Einteilung>,
+    value: f64,
+    einstellung: impl Fn(f64) -> Einstellung + 'static,
+) -> Html {
+    let onchange = ctx.link().batch_callback(move |event: onchange::Event| {
+        let input = event.target_unchecked_into::<HtmlInputElement>();
+        str::parse::<f64>(&input.value())
+            .ok()
+            .map(|value| Msg::Einstellung(einstellung(value)))
+    });
+
+    html! (<input type="number" step="0.5" value={ value.to_string() } { onchange } />)
+}
+
+fn checkbox_input(
+    ctx: &Context<Einteilung>,
+    value: bool,
+    einstellung: impl Fn(bool) -> Einstellung + 'static,
+) -> Html {
+    let onchange = ctx.link().callback(move |event: onchange::Event| {
+        let input = event.target_unchecked_into::<HtmlInputElement>();
+        Msg::Einstellung(einstellung(input.checked()))
+    });
+
+    html! (<input type="checkbox" checked={ value } { onchange } />)
+}
+
+fn gesperrte_zuordnung(data: &Data) -> BTreeMap<SchuelerId, ProjektId> {
+    // Manuell geänderte Zuordnungen bleiben beim erneuten Lösen erhalten
+    if data.solver_settings.keep_manual {
+        data.zuordnung
+            .iter()
+            .filter(|z| z.gesperrt)
+            .filter_map(|z| {
+                z.projekt
+                    .filter(|p_id| data.projekte.contains_key(p_id))
+                    .map(|p_id| (z.schueler, p_id))
+            })
+            .collect::<BTreeMap<SchuelerId, ProjektId>>()
+    } else {
+        BTreeMap::new()
+    }
+}
+
+pub fn solver_anfrage(data: &Data) -> SolverAnfrage {
     log!("Start solve!");
 
-    let feste_zuordnung = BTreeMap::new();
+    let mut feste_zuordnung = data.feste_zuordnung.clone();
+
+    for (schueler_id, projekt_id) in gesperrte_zuordnung(data) {
+        feste_zuordnung.entry(schueler_id).or_insert(projekt_id);
+    }
+
+    SolverAnfrage {
+        projekte: data
+            .projekte
+            .iter()
+            .map(|(&p_id, project)| (p_id, project.clone().into()))
+            .collect::<BTreeMap<ProjektId, Projekt>>(),
+        schueler: data.schueler.clone(),
+        feste_zuordnung,
+        settings: data.solver_settings.clone(),
+    }
+}
 
-    let projekte = &data
+// Das Ergebnis wird auf den aktuellen Stand angewendet, damit Änderungen während des Laufs erhalten
+// bleiben. Die Indizes im Ergebnis beziehen sich auf den Stand beim Start (`gestartet`).
+pub fn solver_ergebnis(data: &Data, gestartet: &Data, result: SolverResult) -> Data {
+    let gesperrt = gesperrte_zuordnung(data);
+
+    let solver_projekte_id_to_projekte_id = gestartet
         .projekte
         .iter()
-        .map(|(&p_id, project)| (p_id, project.clone().into()))
-        .collect::<BTreeMap<ProjektId, Projekt>>();
+        .enumerate()
+        .map(|(id, (&p_id, _))| (id, p_id))
+        .collect::<HashMap<usize, ProjektId>>();
 
-    let schueler = &data.schueler;
+    let solver_schueler_id_to_schueler_id = gestartet
+        .schueler
+        .iter()
+        .enumerate()
+        .map(|(id, (&s_id, _))| (id, s_id))
+        .collect::<HashMap<usize, SchuelerId>>();
+
+    let mut verteilung: Vec<SaveFileZuordnung> = Vec::new();
+
+    log!("Verteilung done!");
+
+    for (schueler_idx, schueler_result) in result.x.iter().enumerate() {
+        let schueler_id = solver_schueler_id_to_schueler_id.get(&schueler_idx);
+        let projekt_index = schueler_result.iter().position(|wert| wert >= &0.5);
+        let projekt_id =
+            projekt_index.and_then(|p_idx| solver_projekte_id_to_projekte_id.get(&p_idx));
+
+        if let Some(schueler_id) = schueler_id {
+            // Während des Laufs gesperrte Zuordnungen gehen vor
+            let projekt = gesperrt.get(schueler_id).or(projekt_id).cloned();
+
+            verteilung.push(SaveFileZuordnung {
+                id: schueler_idx as u32,
+                schueler: *schueler_id,
+                projekt,
+                gesperrt: gesperrt.contains_key(schueler_id),
+            });
+        }
+    }
 
-    let result = solve_good_lp(projekte, schueler, &feste_zuordnung);
+    log!("Solved!");
 
-    if let Ok(result) = result {
-        let solver_projekte_id_to_projekte_id = projekte
-            .iter()
-            .enumerate()
-            .map(|(id, (&p_id, _))| (id, p_id))
-            .collect::<HashMap<usize, ProjektId>>();
+    let mut data = data.clone();
 
-        let solver_schueler_id_to_schueler_id = schueler
-            .iter()
-            .enumerate()
-            .map(|(id, (&s_id, _))| (id, s_id))
-            .collect::<HashMap<usize, SchuelerId>>();
-
-        let mut verteilung: Vec<SaveFileZuordnung> = Vec::new();
-
-        log!("Verteilung done!");
-
-        for (schueler_idx, schueler_result) in result.iter().enumerate() {
-            let schueler_id = solver_schueler_id_to_schueler_id.get(&schueler_idx);
-            let projekt_index = schueler_result.iter().position(|wert| wert >= &0.5);
-            let projekt_id =
-                projekt_index.and_then(|p_idx| solver_projekte_id_to_projekte_id.get(&p_idx));
-
-            if let Some(schueler_id) = schueler_id {
-                verteilung.push(SaveFileZuordnung {
-                    id: schueler_idx as u32,
-                    schueler: *schueler_id,
-                    projekt: projekt_id.cloned(),
-                });
-            }
+    for (projekt_id, projekt) in data.projekte.iter_mut() {
+        projekt.abgesagt = result.cancelled.contains(projekt_id);
+    }
+
+    data.zuordnung = verteilung;
+    // Schüler und Projekte können während des Laufs entfernt worden sein
+    data.bereinigen();
+    data.teilnehmer_zaehlen();
+    data.report = Some(report_aus_zuordnung(&data));
+
+    // Jeder Lauf wird als Snapshot aufgehoben, damit er später verglichen werden kann.
+    // Von den automatischen Snapshots bleiben nur die letzten erhalten.
+    let lauf = data
+        .snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.lauf)
+        .max()
+        .unwrap_or_default()
+        + 1;
+    let mut snapshot = Snapshot::new(format!("Lauf {lauf}"), &data);
+    snapshot.solver_settings = gestartet.solver_settings.clone();
+    snapshot.lauf = Some(lauf);
+    data.snapshots.push(snapshot);
+
+    let mut ueberzaehlig = data
+        .snapshots
+        .iter()
+        .filter(|snapshot| snapshot.lauf.is_some())
+        .count()
+        .saturating_sub(MAX_LAEUFE);
+    data.snapshots.retain(|snapshot| {
+        let entfernen = snapshot.lauf.is_some() && ueberzaehlig > 0;
+        if entfernen {
+            ueberzaehlig -= 1;
         }
+        !entfernen
+    });
 
-        log!("Solved!");
+    data
+}
 
-        Some(verteilung)
-    } else {
-        log!("Couldn't solve!");
-        None
-    }
+// Nach manuellen Änderungen wird die Statistik aus der aktuellen Zuordnung neu berechnet
+fn report_aus_zuordnung(data: &Data) -> SolveReport {
+    let zuordnung = data
+        .zuordnung
+        .iter()
+        .filter_map(|z| z.projekt.map(|p_id| (z.schueler, p_id)))
+        .collect::<BTreeMap<SchuelerId, ProjektId>>();
+
+    SolveReport::new(&data.schueler, &zuordnung)
 }
 
 #[derive(Clone, Serialize, Debug, Default)]
@@ -308,6 +774,8 @@ pub struct EinteilungTableLine {
     pub projekt_id: Option<ProjektId>,
     pub projekt_name: String,
     pub wuensche: Option<[ProjektId; 5]>,
+    pub fest: bool,
+    pub gesperrt: bool,
 }
 
 impl EinteilungTableLine {
@@ -334,6 +802,11 @@ impl EinteilungTableLine {
             schueler_name: schueler.name.clone(),
             projekt_name,
             wuensche: schueler.wishes,
+            fest: data.feste_zuordnung.contains_key(&schueler_id),
+            gesperrt: data
+                .zuordnung
+                .iter()
+                .any(|z| z.schueler == schueler_id && z.gesperrt),
         }
     }
 }
@@ -352,6 +825,57 @@ impl PartialOrd for EinteilungTableLine {
 
 pub enum Edit {
     Projekt { projekt_id: ProjektId },
+    Fest { value: bool },
+    Entsperren,
+}
+
+#[derive(Properties, PartialEq)]
+struct EntsperrenButtonProps {
+    schueler_id: SchuelerId,
+}
+
+#[function_component(EntsperrenButton)]
+fn entsperren_button(props: &EntsperrenButtonProps) -> Html {
+    let on_change = use_context::<Callback<(SchuelerId, Edit)>>();
+
+    let Some(on_change) = on_change else {
+        return html!(<></>);
+    };
+
+    let schueler_id = props.schueler_id;
+
+    let onclick = Callback::from(move |_| on_change.emit((schueler_id, Edit::Entsperren)));
+
+    html! (<button class="gesperrt" title="Wird beim Lösen beibehalten" { onclick }>{ "Entsperren" }</button>)
+}
+
+#[derive(Properties, PartialEq)]
+struct FestCheckboxProps {
+    schueler_id: SchuelerId,
+    value: bool,
+    disabled: bool,
+}
+
+#[function_component(FestCheckbox)]
+fn fest_checkbox(props: &FestCheckboxProps) -> Html {
+    let on_change = use_context::<Callback<(SchuelerId, Edit)>>();
+
+    let Some(on_change) = on_change else {
+        return html!(<></>);
+    };
+
+    let schueler_id = props.schueler_id;
+
+    let onchange = Callback::from(move |event: onchange::Event| {
+        let event = event.target();
+        if let Some(event) = event {
+            let value = event.unchecked_into::<HtmlInputElement>().checked();
+
+            on_change.emit((schueler_id, Edit::Fest { value }))
+        }
+    });
+
+    html! (<input type="checkbox" checked={ props.value } disabled={ props.disabled } { onchange } />)
 }
 
 #[derive(Properties, PartialEq)]
@@ -377,7 +901,11 @@ fn project_selection(props: &ProjektSelectProps) -> Html {
     let onchange = Callback::from(move |event: onchange::Event| {
         let event = event.target();
         if let Some(event) = event {
-            let projekt_id = event.unchecked_into::<HtmlInputElement>().value().into();
+            let Ok(projekt_id) =
+                ProjektId::try_from(event.unchecked_into::<HtmlInputElement>().value())
+            else {
+                return;
+            };
 
             on_change.emit((schueler_id, Edit::Projekt { projekt_id }))
         }
@@ -435,6 +963,16 @@ impl TableData for EinteilungTableLine {
 
                 Ok(html!(<span>{wuensche.map(|w| w.to_string()).join(", ")}</span>))
             }
+            "fest" => Ok(html! {
+                <span><FestCheckbox schueler_id={ self.schueler_id } value={ self.fest } disabled={ self.projekt_id.is_none() } /></span>
+            }),
+            "gesperrt" => {
+                if self.gesperrt {
+                    Ok(html! (<span><EntsperrenButton schueler_id={ self.schueler_id } /></span>))
+                } else {
+                    Ok(html! (<span>{"---"}</span>))
+                }
+            }
             _ => Ok(html! {}),
         }
     }
@@ -456,6 +994,8 @@ impl TableData for EinteilungTableLine {
             "schueler_klasse" => Ok(serde_value::Value::U32(
                 self.schueler_klasse.stufe().unwrap_or(0),
             )),
+            "fest" => Ok(serde_value::Value::Bool(self.fest)),
+            "gesperrt" => Ok(serde_value::Value::Bool(self.gesperrt)),
             _ => Ok(serde_value::to_value(()).unwrap()),
         }
     }
@@ -1,13 +1,22 @@
 use std::collections::{BTreeMap, HashMap};
 
-use gloo::utils::document;
-use gloo_console::log;
-use gloo_file::{Blob, ObjectUrl, callbacks::FileReader};
-use web_sys::{FileList, HtmlInputElement, wasm_bindgen::JsCast};
+use gloo_file::callbacks::FileReader;
+use web_sys::{FileList, HtmlInputElement};
 use yew::{Component, Context, ContextHandle, Event, Html, TargetCast, html};
 
 use crate::{
-    DataContext,
+    Data, DataContext,
+    components::{ImportVorschau, SpaltenAuswahl},
+    export::{csv_exportieren, herunterladen, xlsx_exportieren},
+    import::{
+        AbgelehnteZeile, ImportTabelle,
+        projekte::{self, ImportProjekt, projekte_lesen, projekte_zusammenfuehren},
+        schueler::{
+            self, schueler_lesen, schuelerliste_abgleichen, wahl_abgleichen, wahl_aus_tabelle,
+        },
+        vorschau::{Aenderung, aenderungen_anwenden, aenderungen_berechnen},
+    },
+    seiten::match_all_partner,
     types::{SaveFile, SaveFileSchueler, SchuelerId, schueler_file, schueler_liste_file},
 };
 
@@ -16,17 +25,29 @@ pub enum FileType {
     Full,
     Projekte,
     Schueler,
+    SchuelerTabelle,
     SchuelerListe,
 }
 
 pub enum Msg {
     FileLoaded(String, String),
-    ProjekteLoaded(String, String),
+    ProjekteLoaded(String, Vec<u8>),
+    ImportSpalte(usize, Option<usize>),
+    ImportMerkmal(usize),
+    Importieren,
+    ImportAbbrechen,
+    VorschauUmschalten(usize),
+    VorschauAlle(bool),
+    VorschauUebernehmen,
+    VorschauVerwerfen,
     SchuelerLoaded(String, String),
+    SchuelerTabelleLoaded(String, Vec<u8>),
     SchuelerListeLoaded(String, String),
+    FileError(String, String),
     FileLoad(Option<FileList>, FileType),
     DataUpdate(DataContext),
     SaveFile,
+    ExportXlsx,
     ExportCsv,
 }
 
@@ -34,6 +55,97 @@ pub struct Home {
     readers: HashMap<String, FileReader>,
     data: DataContext,
     _context_listener: ContextHandle<DataContext>,
+    import: Option<LaufenderImport>,
+    vorschau: Option<ImportVorschauDaten>,
+    import_meldung: Option<String>,
+    abgelehnt: Vec<AbgelehnteZeile>,
+    // Schüler mit Wahl, die nicht in der Schülerliste stehen
+    fehlend: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ImportArt {
+    Projekte,
+    Schueler,
+}
+
+struct LaufenderImport {
+    art: ImportArt,
+    tabelle: ImportTabelle,
+    zuordnung: Vec<Option<usize>>,
+    // Zusätzliche Spalten, die als Merkmale der Schüler übernommen werden
+    merkmale: Vec<usize>,
+}
+
+// Importierte Daten werden erst nach Prüfung der einzelnen Änderungen übernommen
+struct ImportVorschauDaten {
+    importiert: Data,
+    // Eintrag im Verlauf, z.B. "Schülerliste importiert"
+    beschreibung: String,
+    aenderungen: Vec<Aenderung>,
+    angenommen: Vec<bool>,
+}
+
+impl Home {
+    fn vorschau_starten(&mut self, importiert: Data, beschreibung: &str) {
+        let aenderungen = aenderungen_berechnen(&self.data, &importiert);
+
+        if aenderungen.is_empty() {
+            self.vorschau = None;
+            self.import_meldung = Some("Der Import enthält keine Änderungen".to_string());
+            return;
+        }
+
+        self.vorschau = Some(ImportVorschauDaten {
+            importiert,
+            beschreibung: beschreibung.to_string(),
+            angenommen: vec![true; aenderungen.len()],
+            aenderungen,
+        });
+    }
+
+    fn projekte_uebernehmen(&mut self, projekte: Vec<ImportProjekt>) {
+        let mut data = self.data.get();
+
+        let (neu, aktualisiert) = projekte_zusammenfuehren(&mut data.projekte, projekte);
+
+        self.import_meldung = Some(format!(
+            "{neu} neue und {aktualisiert} bestehende Projekte gelesen, {} Zeilen abgelehnt",
+            self.abgelehnt.len()
+        ));
+
+        self.vorschau_starten(data, "Projekte importiert");
+    }
+
+    fn tabelle_laden(&mut self, art: ImportArt, name: &str, bytes: &[u8]) {
+        self.abgelehnt = Vec::new();
+        self.fehlend = Vec::new();
+
+        let felder = match art {
+            ImportArt::Projekte => projekte::FELDER,
+            ImportArt::Schueler => schueler::FELDER,
+        };
+
+        match ImportTabelle::lesen(name, bytes) {
+            Ok(tabelle) => {
+                let zuordnung = tabelle.zuordnung_raten(felder);
+                let merkmale = match art {
+                    ImportArt::Projekte => Vec::new(),
+                    ImportArt::Schueler => {
+                        tabelle.spalten_raten(schueler::MERKMAL_ALIASE, &zuordnung)
+                    }
+                };
+                self.import = Some(LaufenderImport {
+                    art,
+                    tabelle,
+                    zuordnung,
+                    merkmale,
+                });
+                self.import_meldung = None;
+            }
+            Err(fehler) => self.import_meldung = Some(fehler.to_string()),
+        }
+    }
 }
 
 impl Component for Home {
@@ -50,6 +162,11 @@ impl Component for Home {
             data,
             _context_listener: context_listener,
             readers: HashMap::default(),
+            import: None,
+            vorschau: None,
+            import_meldung: None,
+            abgelehnt: Vec::new(),
+            fehlend: Vec::new(),
         }
     }
 
@@ -57,222 +174,376 @@ impl Component for Home {
         match msg {
             Msg::FileLoaded(name, text) => {
                 self.readers.remove(&name);
-                log!(text.clone());
 
-                let save_file_data: Result<SaveFile, serde_json::Error> =
-                    serde_json::from_str(&text.clone());
+                let save_file_data = SaveFile::lesen(text.as_bytes());
+
+                self.abgelehnt = Vec::new();
+                self.fehlend = Vec::new();
 
                 match save_file_data {
                     Ok(save_file) => {
                         save_file.log();
-                        let _ = save_file.save_to_local_storage();
-                        self.data.set(save_file.into());
+                        self.vorschau_starten(save_file.into(), "Speicherstand geladen");
+                    }
+                    Err(err) => {
+                        self.import_meldung =
+                            Some(format!("Speicherstand kann nicht gelesen werden: {err}"))
                     }
-                    Err(err) => log!(err.to_string()),
                 }
 
                 true
             }
+            Msg::FileError(name, err) => {
+                self.readers.remove(&name);
+                self.import_meldung = Some(format!("Datei kann nicht gelesen werden: {err}"));
+
+                true
+            }
             Msg::FileLoad(files, file_type) => {
-                log!("LOAD!");
-                for file in gloo::file::FileList::from(files.expect("files")).iter() {
+                let Some(files) = files else {
+                    return false;
+                };
+
+                for file in gloo::file::FileList::from(files).iter() {
                     let link = ctx.link().clone();
                     let name = file.name().clone();
                     let _file_type = file.raw_mime_type();
 
-                    let task = {
-                        gloo::file::callbacks::read_as_text(file, move |text| match file_type {
-                            FileType::Full => link.send_message(Msg::FileLoaded(
-                                name,
-                                text.expect("Failed to read file"),
-                            )),
-                            FileType::Projekte => link.send_message(Msg::ProjekteLoaded(
-                                name,
-                                text.expect("Failed to read file"),
-                            )),
-                            FileType::Schueler => link.send_message(Msg::SchuelerLoaded(
-                                name,
-                                text.expect("Failed to read file"),
-                            )),
-                            FileType::SchuelerListe => link.send_message(Msg::SchuelerListeLoaded(
-                                name,
-                                text.expect("Failed to read file"),
-                            )),
-                        })
+                    let task = match file_type {
+                        FileType::Full => gloo::file::callbacks::read_as_text(file, move |text| {
+                            link.send_message(match text {
+                                Ok(text) => Msg::FileLoaded(name, text),
+                                Err(err) => Msg::FileError(name, err.to_string()),
+                            })
+                        }),
+                        FileType::Projekte => {
+                            gloo::file::callbacks::read_as_bytes(file, move |bytes| {
+                                link.send_message(match bytes {
+                                    Ok(bytes) => Msg::ProjekteLoaded(name, bytes),
+                                    Err(err) => Msg::FileError(name, err.to_string()),
+                                })
+                            })
+                        }
+                        FileType::SchuelerTabelle => {
+                            gloo::file::callbacks::read_as_bytes(file, move |bytes| {
+                                link.send_message(match bytes {
+                                    Ok(bytes) => Msg::SchuelerTabelleLoaded(name, bytes),
+                                    Err(err) => Msg::FileError(name, err.to_string()),
+                                })
+                            })
+                        }
+                        FileType::Schueler => {
+                            gloo::file::callbacks::read_as_text(file, move |text| {
+                                link.send_message(match text {
+                                    Ok(text) => Msg::SchuelerLoaded(name, text),
+                                    Err(err) => Msg::FileError(name, err.to_string()),
+                                })
+                            })
+                        }
+                        FileType::SchuelerListe => {
+                            gloo::file::callbacks::read_as_text(file, move |text| {
+                                link.send_message(match text {
+                                    Ok(text) => Msg::SchuelerListeLoaded(name, text),
+                                    Err(err) => Msg::FileError(name, err.to_string()),
+                                })
+                            })
+                        }
                     };
                     self.readers.insert(file.name(), task);
                 }
                 true
             }
-            Msg::ProjekteLoaded(name, text) => {
+            Msg::ProjekteLoaded(name, bytes) => {
                 self.readers.remove(&name);
-                log!(text.clone());
+                self.abgelehnt = Vec::new();
+                self.fehlend = Vec::new();
+
+                // Speicherstände enthalten die Projekte bereits im richtigen Format
+                if name.to_lowercase().ends_with(".json") {
+                    match SaveFile::lesen(&bytes) {
+                        Ok(save_file) => self.projekte_uebernehmen(
+                            save_file
+                                .projekte
+                                .into_values()
+                                .map(ImportProjekt::from)
+                                .collect(),
+                        ),
+                        Err(err) => {
+                            self.import_meldung =
+                                Some(format!("Datei kann nicht gelesen werden: {err}"))
+                        }
+                    }
 
-                let save_file_data: Result<SaveFile, serde_json::Error> =
-                    serde_json::from_str(&text.clone());
+                    return true;
+                }
 
-                match save_file_data {
-                    Ok(save_file) => {
-                        save_file.log();
-                        let _ = save_file.save_to_local_storage();
-                        self.data.set(save_file.into());
+                self.tabelle_laden(ImportArt::Projekte, &name, &bytes);
+
+                true
+            }
+            Msg::SchuelerTabelleLoaded(name, bytes) => {
+                self.readers.remove(&name);
+                self.tabelle_laden(ImportArt::Schueler, &name, &bytes);
+
+                true
+            }
+            Msg::ImportSpalte(feld, spalte) => {
+                if let Some(import) = self.import.as_mut() {
+                    import.zuordnung[feld] = spalte;
+                    import.merkmale.retain(|&merkmal| Some(merkmal) != spalte);
+                }
+
+                true
+            }
+            Msg::ImportMerkmal(spalte) => {
+                if let Some(import) = self.import.as_mut() {
+                    if import.merkmale.contains(&spalte) {
+                        import.merkmale.retain(|&merkmal| merkmal != spalte);
+                    } else {
+                        import.merkmale.push(spalte);
                     }
-                    Err(err) => log!(err.to_string()),
                 }
 
                 true
             }
+            Msg::Importieren => {
+                let Some(import) = self.import.take() else {
+                    return false;
+                };
+
+                match import.art {
+                    ImportArt::Projekte => {
+                        let (projekte, abgelehnt) =
+                            projekte_lesen(&import.tabelle, &import.zuordnung);
+
+                        self.abgelehnt = abgelehnt;
+                        self.projekte_uebernehmen(projekte);
+                    }
+                    ImportArt::Schueler => {
+                        let mut data = self.data.get();
+
+                        let (neue_schueler, abgelehnt) = schueler_lesen(
+                            &import.tabelle,
+                            &import.zuordnung,
+                            &import.merkmale,
+                            &data.projekte,
+                        );
+
+                        let schueler_wuensche = wahl_aus_tabelle(&data.schueler, neue_schueler);
+                        let abgleich = wahl_abgleichen(&mut data.schueler, schueler_wuensche);
+                        data.schueler = match_all_partner(&data.schueler);
+
+                        self.import_meldung = Some(format!(
+                            "{} Wahlen zugeordnet, {} Schüler neu, {} Zeilen abgelehnt",
+                            abgleich.zugeordnet,
+                            abgleich.neu,
+                            abgelehnt.len()
+                        ));
+                        self.fehlend = abgleich.fehlend;
+
+                        self.vorschau_starten(data, "Schülerwahl importiert");
+                        self.abgelehnt = abgelehnt;
+                    }
+                }
+
+                true
+            }
+            Msg::ImportAbbrechen => {
+                self.import = None;
+
+                true
+            }
+            Msg::VorschauUmschalten(idx) => {
+                if let Some(angenommen) = self
+                    .vorschau
+                    .as_mut()
+                    .and_then(|vorschau| vorschau.angenommen.get_mut(idx))
+                {
+                    *angenommen = !*angenommen;
+                }
+
+                true
+            }
+            Msg::VorschauAlle(wert) => {
+                if let Some(vorschau) = self.vorschau.as_mut() {
+                    vorschau.angenommen.iter_mut().for_each(|a| *a = wert);
+                }
+
+                true
+            }
+            Msg::VorschauUebernehmen => {
+                let Some(vorschau) = self.vorschau.take() else {
+                    return false;
+                };
+
+                let angenommen = vorschau
+                    .aenderungen
+                    .iter()
+                    .zip(vorschau.angenommen.iter())
+                    .filter(|&(_, &angenommen)| angenommen)
+                    .map(|(aenderung, _)| aenderung);
+
+                let data = aenderungen_anwenden(&self.data, &vorschau.importiert, angenommen);
+
+                self.import_meldung = Some(format!(
+                    "{} von {} Änderungen übernommen",
+                    vorschau.angenommen.iter().filter(|&&a| a).count(),
+                    vorschau.aenderungen.len()
+                ));
+
+                self.data.aendern(data, vorschau.beschreibung);
+
+                true
+            }
+            Msg::VorschauVerwerfen => {
+                self.vorschau = None;
+                self.import_meldung = Some("Import verworfen".to_string());
+
+                true
+            }
             Msg::SchuelerLoaded(name, text) => {
                 self.readers.remove(&name);
-                log!(text.clone());
 
                 let save_file_data: Result<schueler_file::SchuelerFile, serde_json::Error> =
                     serde_json::from_str(&text.clone());
 
-                match save_file_data {
+                self.abgelehnt = Vec::new();
+                self.fehlend = Vec::new();
+
+                let schueler_wuensche = match save_file_data {
                     Ok(schueler_file) => {
-                        // let _ = schueler_file.save_to_local_storage();
+                        BTreeMap::<SchuelerId, SaveFileSchueler>::try_from(schueler_file)
+                    }
+                    Err(err) => {
+                        self.import_meldung =
+                            Some(format!("Schülerwahl kann nicht gelesen werden: {err}"));
+                        return true;
+                    }
+                };
 
+                match schueler_wuensche {
+                    Ok(schueler_wuensche) => {
                         let mut data = self.data.get();
 
-                        let mut schueler = data.schueler;
-
-                        let schueler_wuensche: BTreeMap<SchuelerId, SaveFileSchueler> =
-                            schueler_file.into();
-
-                        for (schueler_id, schueler_data) in schueler_wuensche {
-                            schueler.insert(schueler_id, schueler_data);
-                        }
-
-                        data.schueler = schueler;
+                        let abgleich = wahl_abgleichen(&mut data.schueler, schueler_wuensche);
+                        data.schueler = match_all_partner(&data.schueler);
 
-                        self.data.set(data);
+                        self.import_meldung = Some(format!(
+                            "{} Wahlen zugeordnet, {} Schüler neu",
+                            abgleich.zugeordnet, abgleich.neu
+                        ));
+                        self.fehlend = abgleich.fehlend;
+                        self.vorschau_starten(data, "Schülerwahl importiert");
+                    }
+                    Err(abgelehnt) => {
+                        self.import_meldung = Some(format!(
+                            "Schülerwahl nicht importiert, {} Einträge sind fehlerhaft",
+                            abgelehnt.len()
+                        ));
+                        self.abgelehnt = abgelehnt;
                     }
-                    Err(err) => log!(err.to_string()),
                 }
 
                 true
             }
             Msg::SchuelerListeLoaded(name, text) => {
                 self.readers.remove(&name);
-                log!(text.clone());
 
                 let save_file_data: Result<
                     schueler_liste_file::SchuelerListeFile,
                     serde_json::Error,
                 > = serde_json::from_str(&text.clone());
 
-                match save_file_data {
+                self.abgelehnt = Vec::new();
+                self.fehlend = Vec::new();
+
+                let schueler_liste = match save_file_data {
                     Ok(schueler_file) => {
-                        // let _ = schueler_file.save_to_local_storage();
+                        BTreeMap::<SchuelerId, SaveFileSchueler>::try_from(schueler_file)
+                    }
+                    Err(err) => {
+                        self.import_meldung =
+                            Some(format!("Schülerliste kann nicht gelesen werden: {err}"));
+                        return true;
+                    }
+                };
 
+                match schueler_liste {
+                    Ok(schueler_liste) => {
                         let mut data = self.data.get();
 
-                        data.schueler = schueler_file.into();
+                        let abgleich = schuelerliste_abgleichen(&mut data.schueler, schueler_liste);
 
-                        self.data.set(data);
+                        self.import_meldung = Some(format!(
+                            "{} Schüler zugeordnet, {} Schüler neu",
+                            abgleich.zugeordnet, abgleich.neu
+                        ));
+                        self.fehlend = abgleich.fehlend;
+                        self.vorschau_starten(data, "Schülerliste importiert");
+                    }
+                    Err(abgelehnt) => {
+                        self.import_meldung = Some(format!(
+                            "Schülerliste nicht importiert, {} Einträge sind fehlerhaft",
+                            abgelehnt.len()
+                        ));
+                        self.abgelehnt = abgelehnt;
                     }
-                    Err(err) => log!(err.to_string()),
                 }
 
                 true
             }
             Msg::DataUpdate(data) => {
                 self.data = data;
-                let _ = self.data.save();
 
                 true
             }
             Msg::SaveFile => {
-                let data = self.data.get();
-
-                let json_string =
-                    serde_json::to_string_pretty(&data).expect("Fehler beim Serialisieren");
-
-                let blob = Blob::new_with_options(json_string.as_str(), Some("application/json"));
-
-                let url = ObjectUrl::from(blob);
-
-                let document = document();
-                let a = document
-                    .create_element("a")
-                    .unwrap()
-                    .dyn_into::<web_sys::HtmlAnchorElement>()
-                    .unwrap();
-
-                a.set_href(&url);
-                a.set_download("projekttage.json");
-                a.click();
+                let save_file = SaveFile::from(self.data.get());
+
+                match serde_json::to_string_pretty(&save_file) {
+                    Ok(json_string) => herunterladen(
+                        "projekttage.json",
+                        "application/json",
+                        json_string.as_bytes(),
+                    ),
+                    Err(fehler) => {
+                        self.import_meldung = Some(format!(
+                            "Speicherstand kann nicht erstellt werden: {fehler}"
+                        ));
+                        return true;
+                    }
+                }
 
                 false
             }
-            Msg::ExportCsv => {
-                let data = self.data.get();
-                let zuordnungen = data.clone().zuordnung;
-
-                let mut csv_string = "ID;Schueler;Wünsche;Projekt".to_string();
-
-                for zuordnung in zuordnungen {
-                    let schueler = data.get_schueler(&zuordnung.schueler).unwrap();
-
-                    let projekt = zuordnung.projekt.and_then(|p_id| data.get_projekt(&p_id));
-
-                    csv_string += format!(
-                        "\n{};{} ({});{};{}",
-                        zuordnung.id,
-                        schueler.name,
-                        schueler.klasse.klasse(),
-                        schueler
-                            .wishes
-                            .map(|wishes| wishes
-                                .iter()
-                                .filter_map(|w| if w.id() != u32::MAX {
-                                    Some(format!(
-                                        "{}: {}",
-                                        w.id(),
-                                        data.get_projekt(w)
-                                            .map(|p| p.name.as_str())
-                                            .unwrap_or("---")
-                                    ))
-                                } else {
-                                    None
-                                })
-                                .collect::<Vec<String>>()
-                                .join(", "))
-                            .unwrap_or("---".to_string()),
-                        zuordnung
-                            .projekt
-                            .map_or("---".to_string(), |p_id| projekt.map_or(
-                                "---".to_string(),
-                                |p| format!(
-                                    "{}: {} ({}-{})",
-                                    p_id.id(),
-                                    p.name,
-                                    p.min_stufe,
-                                    p.max_stufe
-                                )
-                            )),
-                    )
-                    .as_str();
+            Msg::ExportXlsx => match xlsx_exportieren(&self.data) {
+                Ok(inhalt) => {
+                    herunterladen(
+                        "projekttage.xlsx",
+                        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+                        &inhalt,
+                    );
+
+                    false
                 }
+                Err(fehler) => {
+                    self.import_meldung = Some(fehler.to_string());
 
-                let blob = Blob::new_with_options(csv_string.as_str(), Some("application/csv"));
-
-                let url = ObjectUrl::from(blob);
-
-                let document = document();
-                let a = document
-                    .create_element("a")
-                    .unwrap()
-                    .dyn_into::<web_sys::HtmlAnchorElement>()
-                    .unwrap();
+                    true
+                }
+            },
+            Msg::ExportCsv => match csv_exportieren(&self.data) {
+                Ok(inhalt) => {
+                    herunterladen("projekttage.csv", "text/csv", &inhalt);
 
-                a.set_href(&url);
-                a.set_download("projekttage.csv");
-                a.click();
+                    false
+                }
+                Err(fehler) => {
+                    self.import_meldung = Some(fehler.to_string());
 
-                false
-            }
+                    true
+                }
+            },
         }
     }
 
@@ -298,7 +569,7 @@ impl Component for Home {
                     <input
                             id="projekte-upload"
                             type="file"
-                            accept="*.json"
+                            accept=".csv,.xlsx,.xls,.ods,.json"
                             multiple={false}
                             onchange={ctx.link().callback(move |e: Event| {
                                 let input: HtmlInputElement = e.target_unchecked_into();
@@ -306,6 +577,36 @@ impl Component for Home {
                             })}
                         />
                 </div>
+                if let Some(vorschau) = &self.vorschau {
+                    <ImportVorschau
+                        aenderungen={ vorschau.aenderungen.clone() }
+                        angenommen={ vorschau.angenommen.clone() }
+                        projekte={ vorschau.importiert.projekte.clone() }
+                        onumschalten={ ctx.link().callback(Msg::VorschauUmschalten) }
+                        onalle={ ctx.link().callback(Msg::VorschauAlle) }
+                        onuebernehmen={ ctx.link().callback(|_| Msg::VorschauUebernehmen) }
+                        onverwerfen={ ctx.link().callback(|_| Msg::VorschauVerwerfen) }
+                    />
+                }
+                if let Some(meldung) = &self.import_meldung {
+                    <p class="import_meldung">{ meldung.clone() }</p>
+                }
+                if !self.abgelehnt.is_empty() {
+                    <div class="abgelehnt">
+                        <h2>{ "Fehlerhafte Einträge" }</h2>
+                        <ul>
+                            { for self.abgelehnt.iter().map(|zeile| html! (<li>{ format!("Zeile {}: {}", zeile.zeile, zeile.grund) }</li>)) }
+                        </ul>
+                    </div>
+                }
+                if !self.fehlend.is_empty() {
+                    <div class="abgelehnt">
+                        <h2>{ "Nicht in der Schülerliste" }</h2>
+                        <ul>
+                            { for self.fehlend.iter().map(|name| html! (<li>{ name.clone() }</li>)) }
+                        </ul>
+                    </div>
+                }
                 <div>
                     <label for="schuelerliste-upload">{"Schülerliste hochladen"}</label>
                     <input
@@ -329,8 +630,39 @@ impl Component for Home {
                                 Msg::FileLoad(input.files(), FileType::Schueler)
                             })}
                         />
+                    <label for="schueler-tabelle-upload">{"Schülerwahl als Tabelle hochladen"}</label>
+                    <input
+                            id="schueler-tabelle-upload"
+                            type="file"
+                            accept=".csv,.xlsx,.xls,.ods"
+                            multiple={false}
+                            onchange={ctx.link().callback(move |e: Event| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::FileLoad(input.files(), FileType::SchuelerTabelle)
+                            })}
+                        />
                 </div>
+                if let Some(import) = &self.import {
+                    <SpaltenAuswahl
+                        titel={ match import.art {
+                            ImportArt::Projekte => "Spalten der Projektliste zuordnen",
+                            ImportArt::Schueler => "Spalten der Schülerwahl zuordnen",
+                        } }
+                        felder={ match import.art {
+                            ImportArt::Projekte => projekte::FELDER,
+                            ImportArt::Schueler => schueler::FELDER,
+                        } }
+                        tabelle={ import.tabelle.clone() }
+                        zuordnung={ import.zuordnung.clone() }
+                        merkmale={ (import.art == ImportArt::Schueler).then(|| import.merkmale.clone()) }
+                        onchange={ ctx.link().callback(|(feld, spalte)| Msg::ImportSpalte(feld, spalte)) }
+                        onmerkmal={ ctx.link().callback(Msg::ImportMerkmal) }
+                        onimport={ ctx.link().callback(|_| Msg::Importieren) }
+                        onabbrechen={ ctx.link().callback(|_| Msg::ImportAbbrechen) }
+                    />
+                }
                 <div>
+                    <button onclick={ctx.link().callback(move |_| Msg::ExportXlsx)}>{"Einteilung als Excel-Datei exportieren"}</button>
                     <button onclick={ctx.link().callback(move |_| Msg::ExportCsv)}>{"Einteilung als CSV exportieren"}</button>
                 </div>
             </div>
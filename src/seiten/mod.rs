@@ -1,11 +1,14 @@
+mod druck;
 mod einteilung;
 mod home;
 mod projekte;
 mod schueler;
 
+pub use druck::Druck;
 pub use einteilung::Einteilung;
 pub use home::Home;
 pub use projekte::Projekte;
 pub use schueler::Schueler;
+pub use schueler::match_all_partner;
 
 pub use projekte::ProjektTableLine;
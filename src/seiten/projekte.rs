@@ -1,21 +1,44 @@
-use std::collections::BTreeMap;
-
-use gloo_console::log;
-use gloo_storage::{LocalStorage, Storage};
+use gloo::dialogs::{alert, confirm};
 use serde::Serialize;
-use yew::{Component, Context, Html, classes, html, use_effect};
+use web_sys::{HtmlInputElement, wasm_bindgen::JsCast};
+use yew::{
+    AttrValue, Callback, Component, Context, ContextHandle, ContextProvider, Html, Properties,
+    classes, function_component, html, html::onchange, use_context,
+};
 use yew_custom_components::table::types::{ColumnBuilder, TableData};
 
 use crate::{
-    Projekt,
+    DataContext, Projekt,
     components::Tabelle,
-    types::{ProjektId, SaveFileProjekt},
+    types::{MerkmalAnteil, ProjektId, SaveFileProjekt, StufenQuote},
 };
 
-pub enum Msg {}
+pub enum Msg {
+    DataUpdate(DataContext),
+    Edit(ProjektId, Edit),
+    Neu,
+    Loeschen(ProjektId),
+}
+
+pub enum Edit {
+    Name { value: String },
+    Raum { value: String },
+    Betreuer { value: String },
+    MinStufe { value: u32 },
+    MaxStufe { value: u32 },
+    MinTeilnehmer { value: i32 },
+    MaxTeilnehmer { value: i32 },
+    Quoten { value: Vec<StufenQuote> },
+    MaxProKlasse { value: Option<u32> },
+    Anteile { value: Vec<MerkmalAnteil> },
+    Ignorieren { value: bool },
+}
 
 pub struct Projekte {
-    projekte: BTreeMap<ProjektId, Projekt>,
+    data: DataContext,
+    onchange: Callback<(ProjektId, Edit)>,
+    onloeschen: Callback<ProjektId>,
+    _context_listener: ContextHandle<DataContext>,
 }
 
 impl Component for Projekte {
@@ -23,46 +46,23 @@ impl Component for Projekte {
 
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        let (data, context_listener) = ctx
+            .link()
+            .context::<DataContext>(ctx.link().callback(Msg::DataUpdate))
+            .expect("Kein Datenkontext");
+
         Self {
-            projekte: LocalStorage::get::<BTreeMap<ProjektId, SaveFileProjekt>>("projekte")
-                .unwrap()
-                .iter()
-                .map(|(&projekt_id, s_f_project)| (projekt_id, s_f_project.clone().into()))
-                .collect::<BTreeMap<ProjektId, Projekt>>(),
+            data,
+            onchange: ctx
+                .link()
+                .callback(|(projekt_id, edit)| Msg::Edit(projekt_id, edit)),
+            onloeschen: ctx.link().callback(Msg::Loeschen),
+            _context_listener: context_listener,
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        // let projekte = use_state(|| {
-        //     vec![
-        //         Projekt {
-        //             id: 0,
-        //             name: "Test".into(),
-        //             stufen: (6..=12),
-        //             teilnehmer: (10..=50),
-        //         },
-        //         Projekt {
-        //             id: 1,
-        //             name: "Test2".into(),
-        //             stufen: (5..=8),
-        //             teilnehmer: (0..=100),
-        //         },
-        //     ]
-        // });
-
-        {
-            let projekte = self.projekte.clone();
-
-            // For storage
-            use_effect(move || {
-                log!(
-                    "projekte changed to: ",
-                    serde_wasm_bindgen::to_value(&projekte.clone()).unwrap()
-                );
-            });
-        }
-
+    fn view(&self, ctx: &Context<Self>) -> Html {
         // Column definition
         let columns = vec![
             ColumnBuilder::new("id")
@@ -77,6 +77,18 @@ impl Component for Projekte {
                 .data_property("name")
                 .header_class("user-select-none")
                 .build(),
+            ColumnBuilder::new("raum")
+                .orderable(true)
+                .short_name("Raum")
+                .data_property("raum")
+                .header_class("user-select-none")
+                .build(),
+            ColumnBuilder::new("betreuer")
+                .orderable(true)
+                .short_name("Betreuer")
+                .data_property("betreuer")
+                .header_class("user-select-none")
+                .build(),
             ColumnBuilder::new("min_stufe")
                 .orderable(true)
                 .short_name("Mindeste Stufe")
@@ -101,34 +113,416 @@ impl Component for Projekte {
                 .data_property("max_teilnehmer")
                 .header_class("user-select-none")
                 .build(),
+            ColumnBuilder::new("quoten")
+                .orderable(false)
+                .short_name("Quoten je Stufe")
+                .data_property("quoten")
+                .header_class("user-select-none")
+                .build(),
+            ColumnBuilder::new("max_pro_klasse")
+                .orderable(false)
+                .short_name("Höchstens je Klasse")
+                .data_property("max_pro_klasse")
+                .header_class("user-select-none")
+                .build(),
+            ColumnBuilder::new("anteile")
+                .orderable(false)
+                .short_name("Anteile je Merkmal")
+                .data_property("anteile")
+                .header_class("user-select-none")
+                .build(),
             ColumnBuilder::new("num_einteilung")
                 .orderable(false)
                 .short_name("Anzahl Schueler nach Einteilung")
                 .data_property("num_einteilung")
                 .header_class("user-select-none")
                 .build(),
+            ColumnBuilder::new("abgesagt")
+                .orderable(true)
+                .short_name("Abgesagt")
+                .data_property("abgesagt")
+                .header_class("user-select-none")
+                .build(),
+            ColumnBuilder::new("ignorieren")
+                .orderable(true)
+                .short_name("Ignorieren")
+                .data_property("ignorieren")
+                .header_class("user-select-none")
+                .build(),
+            ColumnBuilder::new("loeschen")
+                .orderable(false)
+                .short_name("")
+                .data_property("loeschen")
+                .header_class("user-select-none")
+                .build(),
         ];
 
         let mut table_data = Vec::new();
-        for (index, (projekt_id, projekt)) in self.projekte.iter().enumerate() {
+        for (index, (projekt_id, save_file_projekt)) in self.data.projekte.iter().enumerate() {
+            let projekt = Projekt::from(save_file_projekt.clone());
+
             table_data.push(ProjektTableLine {
                 original_index: index,
                 id: *projekt_id,
                 name: projekt.name.clone(),
+                raum: save_file_projekt.raum.clone(),
+                betreuer: save_file_projekt.betreuer.clone(),
                 min_stufe: *projekt.stufen.start(),
                 max_stufe: *projekt.stufen.end(),
                 min_teilnehmer: *projekt.teilnehmer.start(),
                 max_teilnehmer: *projekt.teilnehmer.end(),
+                quoten: save_file_projekt
+                    .quoten
+                    .iter()
+                    .map(StufenQuote::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                max_pro_klasse: save_file_projekt.max_pro_klasse,
+                anteile: save_file_projekt
+                    .anteile
+                    .iter()
+                    .map(MerkmalAnteil::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
                 num_einteilung: projekt.num_einteilung,
+                abweichung: projekt.abweichung(self.data.feste_schueler(projekt_id)),
+                abgesagt: projekt.abgesagt,
+                ignorieren: save_file_projekt.ignore,
             });
         }
 
         html! {
-            <div class="seite">
-                <Tabelle<ProjektTableLine> columns={columns} table_data={table_data} />
-            </div>
+            <ContextProvider<Callback<(ProjektId, Edit)>> context={ self.onchange.clone() }>
+                <ContextProvider<Callback<ProjektId>> context={ self.onloeschen.clone() }>
+                    <div class="seite">
+                        <button onclick={ctx.link().callback(move |_| Msg::Neu)}>{"Neues Projekt"}</button>
+                        <Tabelle<ProjektTableLine> columns={columns} table_data={table_data} />
+                    </div>
+                </ContextProvider<Callback<ProjektId>>>
+            </ContextProvider<Callback<(ProjektId, Edit)>>>
         }
     }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DataUpdate(data) => {
+                self.data = data;
+
+                true
+            }
+            Msg::Neu => {
+                let mut data = self.data.get();
+
+                let projekt_id = data
+                    .projekte
+                    .keys()
+                    .last()
+                    .map(|p_id| ProjektId::new(p_id.id() + 1))
+                    .unwrap_or_default();
+
+                // Neue Projekte stehen allen Stufen offen, die Schüler haben
+                let stufen = data
+                    .schueler
+                    .values()
+                    .filter_map(|s| s.klasse.stufe())
+                    .collect::<Vec<u32>>();
+
+                data.projekte.insert(
+                    projekt_id,
+                    SaveFileProjekt {
+                        name: format!("Neues Projekt {projekt_id}"),
+                        min_teilnehmer: -1,
+                        max_teilnehmer: -1,
+                        min_stufe: stufen.iter().copied().min().unwrap_or(5),
+                        max_stufe: stufen.iter().copied().max().unwrap_or(12),
+                        ignore: false,
+                        num_einteilung: None,
+                        abgesagt: false,
+                        raum: String::new(),
+                        betreuer: String::new(),
+                        quoten: Vec::new(),
+                        max_pro_klasse: None,
+                        anteile: Vec::new(),
+                    },
+                );
+
+                self.data
+                    .aendern(data, format!("Projekt {projekt_id} angelegt"));
+
+                false
+            }
+            Msg::Loeschen(projekt_id) => {
+                let mut data = self.data.get();
+
+                let Some(projekt) = data.projekte.get(&projekt_id) else {
+                    return false;
+                };
+
+                let gewuenscht = data
+                    .schueler
+                    .values()
+                    .filter(|s| s.wishes.is_some_and(|wishes| wishes.contains(&projekt_id)))
+                    .count();
+
+                let frage = if gewuenscht > 0 {
+                    format!(
+                        "{gewuenscht} Schüler haben sich \"{}\" gewünscht. Diese Wünsche gehen beim Löschen verloren. Trotzdem löschen?",
+                        projekt.name
+                    )
+                } else {
+                    format!("\"{}\" löschen?", projekt.name)
+                };
+
+                if !confirm(&frage) {
+                    return false;
+                }
+
+                let beschreibung = format!("Projekt \"{}\" gelöscht", projekt.name);
+
+                data.projekt_entfernen(&projekt_id);
+
+                self.data.aendern(data, beschreibung);
+
+                false
+            }
+            Msg::Edit(projekt_id, edit) => {
+                let mut data = self.data.get();
+
+                let Some(projekt) = data.projekte.get_mut(&projekt_id) else {
+                    return false;
+                };
+
+                let beschreibung = match edit {
+                    Edit::Name { .. } => format!("Name von Projekt {projekt_id} geändert"),
+                    Edit::Raum { .. } => format!("Raum von \"{}\" geändert", projekt.name),
+                    Edit::Betreuer { .. } => format!("Betreuer von \"{}\" geändert", projekt.name),
+                    Edit::MinStufe { .. } | Edit::MaxStufe { .. } => {
+                        format!("Stufen von \"{}\" geändert", projekt.name)
+                    }
+                    Edit::MinTeilnehmer { .. } | Edit::MaxTeilnehmer { .. } => {
+                        format!("Teilnehmerzahl von \"{}\" geändert", projekt.name)
+                    }
+                    Edit::Quoten { .. } | Edit::MaxProKlasse { .. } => {
+                        format!("Quoten von \"{}\" geändert", projekt.name)
+                    }
+                    Edit::Anteile { .. } => format!("Anteile von \"{}\" geändert", projekt.name),
+                    Edit::Ignorieren { value: true } => format!("\"{}\" ignoriert", projekt.name),
+                    Edit::Ignorieren { value: false } => {
+                        format!("\"{}\" nicht mehr ignoriert", projekt.name)
+                    }
+                };
+
+                match edit {
+                    Edit::Name { value } => projekt.name = value,
+                    Edit::Raum { value } => projekt.raum = value.trim().to_string(),
+                    Edit::Betreuer { value } => projekt.betreuer = value.trim().to_string(),
+                    Edit::MinStufe { value } => {
+                        projekt.min_stufe = value;
+                        projekt.max_stufe = projekt.max_stufe.max(value);
+                    }
+                    Edit::MaxStufe { value } => {
+                        projekt.max_stufe = value;
+                        projekt.min_stufe = projekt.min_stufe.min(value);
+                    }
+                    // -1 bedeutet keine Grenze und wird nicht angeglichen
+                    Edit::MinTeilnehmer { value } => {
+                        projekt.min_teilnehmer = value;
+                        if value != -1 && projekt.max_teilnehmer != -1 {
+                            projekt.max_teilnehmer = projekt.max_teilnehmer.max(value);
+                        }
+                    }
+                    Edit::MaxTeilnehmer { value } => {
+                        projekt.max_teilnehmer = value;
+                        if value != -1 && projekt.min_teilnehmer != -1 {
+                            projekt.min_teilnehmer = projekt.min_teilnehmer.min(value);
+                        }
+                    }
+                    Edit::Quoten { value } => projekt.quoten = value,
+                    Edit::MaxProKlasse { value } => projekt.max_pro_klasse = value,
+                    Edit::Anteile { value } => projekt.anteile = value,
+                    Edit::Ignorieren { value } => projekt.ignore = value,
+                }
+
+                self.data.aendern(data, beschreibung);
+
+                true
+            }
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct TextfeldProps {
+    projekt_id: ProjektId,
+    object_key: AttrValue,
+    value: AttrValue,
+}
+
+#[function_component(Textfeld)]
+fn textfeld(props: &TextfeldProps) -> Html {
+    let Some(on_change) = use_context::<Callback<(ProjektId, Edit)>>() else {
+        return html!(<></>);
+    };
+
+    let projekt_id = props.projekt_id;
+    let key = props.object_key.clone();
+    let gespeichert = props.value.clone();
+
+    let onchange = Callback::from(move |event: onchange::Event| {
+        if let Some(event) = event.target() {
+            let input = event.unchecked_into::<HtmlInputElement>();
+            let value = input.value();
+
+            let edit = match key.as_str() {
+                "raum" => Edit::Raum { value },
+                "betreuer" => Edit::Betreuer { value },
+                "quoten" => match StufenQuote::lesen(&value) {
+                    Some(value) => Edit::Quoten { value },
+                    None => {
+                        return eingabe_verwerfen(
+                            &input,
+                            &gespeichert,
+                            "Quoten wie \"5-6: 8, 7-8: 8\"",
+                        );
+                    }
+                },
+                "anteile" => match MerkmalAnteil::lesen(&value) {
+                    Some(value) => Edit::Anteile { value },
+                    None => {
+                        return eingabe_verwerfen(
+                            &input,
+                            &gespeichert,
+                            "Anteile wie \"Geschlecht=w: 40-60%\" (jeweils 0 bis 100%)",
+                        );
+                    }
+                },
+                _ => Edit::Name { value },
+            };
+
+            on_change.emit((projekt_id, edit))
+        }
+    });
+
+    let placeholder = match props.object_key.as_str() {
+        "quoten" => Some("z.B. 5-6: 8, 7-8: 8"),
+        "anteile" => Some("z.B. Geschlecht=w: 40-60%"),
+        _ => None,
+    };
+
+    html! (<input type="text" class={ format!("projekt_{}", props.object_key) } value={props.value.clone()} { placeholder } { onchange } />)
+}
+
+// Nicht lesbare Eingaben werden gemeldet und auf den gespeicherten Wert zurückgesetzt
+fn eingabe_verwerfen(input: &HtmlInputElement, gespeichert: &str, format: &str) {
+    alert(&format!(
+        "\"{}\" konnte nicht gelesen werden. Erwartet werden {format}.",
+        input.value()
+    ));
+    input.set_value(gespeichert);
+}
+
+#[derive(Properties, PartialEq)]
+struct ZahlfeldProps {
+    projekt_id: ProjektId,
+    object_key: AttrValue,
+    value: i32,
+}
+
+#[function_component(Zahlfeld)]
+fn zahlfeld(props: &ZahlfeldProps) -> Html {
+    let Some(on_change) = use_context::<Callback<(ProjektId, Edit)>>() else {
+        return html!(<></>);
+    };
+
+    let projekt_id = props.projekt_id;
+    let key = props.object_key.clone();
+    let optional = key.ends_with("teilnehmer") || key == "max_pro_klasse";
+
+    let onchange = Callback::from(move |event: onchange::Event| {
+        let Some(event) = event.target() else {
+            return;
+        };
+
+        let value = event.unchecked_into::<HtmlInputElement>().value();
+
+        // Leere Teilnehmergrenzen bedeuten "keine Grenze" (-1)
+        let edit = match (key.as_str(), str::parse::<i32>(value.trim())) {
+            ("min_stufe", Ok(value)) if value >= 0 => Edit::MinStufe {
+                value: value as u32,
+            },
+            ("max_stufe", Ok(value)) if value >= 0 => Edit::MaxStufe {
+                value: value as u32,
+            },
+            ("min_teilnehmer", Ok(value)) if value >= 0 => Edit::MinTeilnehmer { value },
+            ("min_teilnehmer", Err(_)) if value.trim().is_empty() => {
+                Edit::MinTeilnehmer { value: -1 }
+            }
+            ("max_teilnehmer", Ok(value)) if value >= 0 => Edit::MaxTeilnehmer { value },
+            ("max_teilnehmer", Err(_)) if value.trim().is_empty() => {
+                Edit::MaxTeilnehmer { value: -1 }
+            }
+            ("max_pro_klasse", Ok(value)) if value > 0 => Edit::MaxProKlasse {
+                value: Some(value as u32),
+            },
+            ("max_pro_klasse", Err(_)) if value.trim().is_empty() => {
+                Edit::MaxProKlasse { value: None }
+            }
+            _ => return,
+        };
+
+        on_change.emit((projekt_id, edit))
+    });
+
+    let value = if optional && props.value == -1 {
+        String::new()
+    } else {
+        props.value.to_string()
+    };
+
+    html! (<input type="number" min="0" step="1" class="projekt_zahl" { value } placeholder={ optional.then_some("beliebig") } { onchange } />)
+}
+
+#[derive(Properties, PartialEq)]
+struct CheckboxProps {
+    projekt_id: ProjektId,
+    value: bool,
+}
+
+#[function_component(Checkbox)]
+fn checkbox(props: &CheckboxProps) -> Html {
+    let Some(on_change) = use_context::<Callback<(ProjektId, Edit)>>() else {
+        return html!(<></>);
+    };
+
+    let projekt_id = props.projekt_id;
+
+    let onchange = Callback::from(move |event: onchange::Event| {
+        if let Some(event) = event.target() {
+            let value = event.unchecked_into::<HtmlInputElement>().checked();
+
+            on_change.emit((projekt_id, Edit::Ignorieren { value }))
+        }
+    });
+
+    html! (<input type="checkbox" checked={props.value} { onchange } />)
+}
+
+#[derive(Properties, PartialEq)]
+struct LoeschenButtonProps {
+    projekt_id: ProjektId,
+}
+
+#[function_component(LoeschenButton)]
+fn loeschen_button(props: &LoeschenButtonProps) -> Html {
+    let Some(on_loeschen) = use_context::<Callback<ProjektId>>() else {
+        return html!(<></>);
+    };
+
+    let projekt_id = props.projekt_id;
+
+    let onclick = Callback::from(move |_| on_loeschen.emit(projekt_id));
+
+    html! (<button class="loeschen" { onclick }>{ "Löschen" }</button>)
 }
 
 #[derive(Clone, Serialize, Debug, Default)]
@@ -136,11 +530,19 @@ pub struct ProjektTableLine {
     pub original_index: usize,
     pub id: ProjektId,
     pub name: String,
+    pub raum: String,
+    pub betreuer: String,
     pub min_stufe: u32,
     pub max_stufe: u32,
     pub min_teilnehmer: i32,
     pub max_teilnehmer: i32,
+    pub quoten: String,
+    pub max_pro_klasse: Option<u32>,
+    pub anteile: String,
     pub num_einteilung: Option<u32>,
+    pub abweichung: Option<i32>,
+    pub abgesagt: bool,
+    pub ignorieren: bool,
 }
 
 impl PartialEq<Self> for ProjektTableLine {
@@ -162,13 +564,42 @@ impl TableData for ProjektTableLine {
     ) -> yew_custom_components::table::error::Result<Html> {
         match field_name {
             "id" => Ok(html! (<span>{format!("{}", self.id)}</span>)),
-            "name" => Ok(html! (<span>{self.name.clone()}</span>)),
-            "min_stufe" => Ok(html! (<span>{self.min_stufe}</span>)),
-            "max_stufe" => Ok(html! (<span>{self.max_stufe}</span>)),
-            "min_teilnehmer" => Ok(html! (<span>{self.min_teilnehmer}</span>)),
-            "max_teilnehmer" => Ok(html! (<span>{self.max_teilnehmer}</span>)),
+            "name" => Ok(
+                html! (<span><Textfeld projekt_id={self.id} object_key="name" value={self.name.clone()} /></span>),
+            ),
+            "raum" => Ok(
+                html! (<span><Textfeld projekt_id={self.id} object_key="raum" value={self.raum.clone()} /></span>),
+            ),
+            "betreuer" => Ok(
+                html! (<span><Textfeld projekt_id={self.id} object_key="betreuer" value={self.betreuer.clone()} /></span>),
+            ),
+            "quoten" => Ok(
+                html! (<span><Textfeld projekt_id={self.id} object_key="quoten" value={self.quoten.clone()} /></span>),
+            ),
+            "anteile" => Ok(
+                html! (<span><Textfeld projekt_id={self.id} object_key="anteile" value={self.anteile.clone()} /></span>),
+            ),
+            "max_pro_klasse" => Ok(
+                html! (<span><Zahlfeld projekt_id={self.id} object_key="max_pro_klasse" value={self.max_pro_klasse.map(|max| max as i32).unwrap_or(-1)} /></span>),
+            ),
+            zahl @ ("min_stufe" | "max_stufe" | "min_teilnehmer" | "max_teilnehmer") => {
+                let value = match zahl {
+                    "min_stufe" => self.min_stufe as i32,
+                    "max_stufe" => self.max_stufe as i32,
+                    "min_teilnehmer" => self.min_teilnehmer,
+                    _ => self.max_teilnehmer,
+                };
+
+                Ok(
+                    html! (<span><Zahlfeld projekt_id={self.id} object_key={zahl.to_string()} { value } /></span>),
+                )
+            }
             "num_einteilung" => {
-                let classes = if self.num_einteilung == Some(self.max_teilnehmer as u32) {
+                let classes = if self.abweichung.is_some_and(|a| a > 0) {
+                    classes!("ueberbelegt")
+                } else if self.abweichung.is_some_and(|a| a < 0) {
+                    classes!("unterbelegt")
+                } else if self.num_einteilung == Some(self.max_teilnehmer as u32) {
                     classes!("voll")
                 } else if self.num_einteilung == Some(self.min_teilnehmer as u32) {
                     classes!("mindestanzahl")
@@ -179,9 +610,20 @@ impl TableData for ProjektTableLine {
                 };
 
                 Ok(
-                    html! (<span class={classes}>{self.num_einteilung.map(|n| n.to_string()).unwrap_or("---".to_string())}</span>),
+                    html! (<span class={classes} title={self.abweichung.map(|a| format!("{a:+}"))}>{self.num_einteilung.map(|n| n.to_string()).unwrap_or("---".to_string())}</span>),
                 )
             }
+            "abgesagt" => {
+                if self.abgesagt {
+                    Ok(html! (<span class="abgesagt">{"Abgesagt"}</span>))
+                } else {
+                    Ok(html! (<span>{"---"}</span>))
+                }
+            }
+            "ignorieren" => Ok(html! {
+                <span><Checkbox projekt_id={self.id} value={self.ignorieren} /></span>
+            }),
+            "loeschen" => Ok(html! (<LoeschenButton projekt_id={self.id} />)),
             _ => Ok(html! {}),
         }
     }
@@ -193,11 +635,15 @@ impl TableData for ProjektTableLine {
         match field_name {
             "id" => Ok(serde_value::Value::U32(self.id.id())),
             "name" => Ok(serde_value::Value::String(self.name.clone())),
+            "raum" => Ok(serde_value::Value::String(self.raum.clone())),
+            "betreuer" => Ok(serde_value::Value::String(self.betreuer.clone())),
             "min_stufe" => Ok(serde_value::Value::U32(self.min_stufe)),
             "max_stufe" => Ok(serde_value::Value::U32(self.max_stufe)),
             "min_teilnehmer" => Ok(serde_value::Value::I32(self.min_teilnehmer)),
             "max_teilnehmer" => Ok(serde_value::Value::I32(self.max_teilnehmer)),
             "num_einteilung" => Ok(serde_value::Value::U32(self.num_einteilung.unwrap_or(0))),
+            "abgesagt" => Ok(serde_value::Value::Bool(self.abgesagt)),
+            "ignorieren" => Ok(serde_value::Value::Bool(self.ignorieren)),
             _ => Ok(serde_value::to_value(()).unwrap()),
         }
     }
@@ -18,8 +18,7 @@ use crate::{
 };
 
 pub enum Msg {
-    DataUpdate(UseStateHandle<Data>),
-    DataSet(Data),
+    DataUpdate(DataContext),
     Edit(SchuelerId, Edit),
 }
 
@@ -54,7 +53,11 @@ fn project_selection(props: &ProjektSelectProps) -> Html {
     let onchange = Callback::from(move |event: onchange::Event| {
         let event = event.target();
         if let Some(event) = event {
-            let projekt_id = event.unchecked_into::<HtmlInputElement>().value().into();
+            let Ok(projekt_id) =
+                ProjektId::try_from(event.unchecked_into::<HtmlInputElement>().value())
+            else {
+                return;
+            };
 
             on_change.emit((
                 schueler_id,
@@ -289,7 +292,7 @@ impl Component for Schueler {
 
         data2.schueler = schueler;
 
-        data.set(data2);
+        data.aendern(data2, "Wunschpartner zugeordnet");
 
         // log!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA");
 
@@ -311,7 +314,7 @@ impl Component for Schueler {
 
     fn view(&self, _ctx: &Context<Self>) -> Html {
         // Column definition
-        let columns = vec![
+        let mut columns = vec![
             ColumnBuilder::new("id")
                 .orderable(true)
                 .short_name("ID")
@@ -380,6 +383,19 @@ impl Component for Schueler {
                 .build(),
         ];
 
+        // Merkmale nur anzeigen, wenn sie mit der Schülerliste importiert wurden
+        if self.data.schueler.values().any(|s| !s.merkmale.is_empty()) {
+            columns.insert(
+                columns.len() - 2,
+                ColumnBuilder::new("merkmale")
+                    .orderable(true)
+                    .short_name("Merkmale")
+                    .data_property("merkmale")
+                    .header_class("user-select-none")
+                    .build(),
+            );
+        }
+
         fn get_wuensche(
             schueler: &SaveFileSchueler,
             data: &Data,
@@ -408,6 +424,12 @@ impl Component for Schueler {
                     (p, partner.name, partner.klasse, partner.partner)
                 }),
                 partner_raw: schueler.partner_raw.clone(),
+                merkmale: schueler
+                    .merkmale
+                    .iter()
+                    .map(|(merkmal, wert)| format!("{merkmal}: {wert}"))
+                    .collect::<Vec<String>>()
+                    .join(", "),
                 fest: schueler.fest.unwrap_or(false),
                 ignorieren: schueler.ignore,
             });
@@ -423,17 +445,10 @@ impl Component for Schueler {
         }
     }
 
-    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::DataUpdate(data) => {
                 self.data = data;
-                let _ = self.data.save();
-
-                true
-            }
-            Msg::DataSet(data) => {
-                self.data.set(data);
-                let _ = self.data.save();
 
                 true
             }
@@ -447,10 +462,29 @@ impl Component for Schueler {
                 if let Some(schueler) = schueler {
                     let mut schueler = schueler.clone();
 
+                    let beschreibung = match &edit {
+                        Edit::Wunsch { idx, .. } => {
+                            format!("Wunsch {} von {} geändert", idx + 1, schueler.name)
+                        }
+                        Edit::Fest { value: true } => {
+                            format!("{} als fest markiert", schueler.name)
+                        }
+                        Edit::Fest { value: false } => {
+                            format!("{} nicht mehr als fest markiert", schueler.name)
+                        }
+                        Edit::Ignorieren { value: true } => format!("{} ignoriert", schueler.name),
+                        Edit::Ignorieren { value: false } => {
+                            format!("{} nicht mehr ignoriert", schueler.name)
+                        }
+                        Edit::Partner { .. } => {
+                            format!("Wunschpartner von {} geändert", schueler.name)
+                        }
+                    };
+
                     match edit {
                         Edit::Wunsch { idx, projekt_id } => {
                             if schueler.wishes.is_none() {
-                                schueler.wishes = Some([ProjektId::from("-1".to_string()); 5]);
+                                schueler.wishes = Some([ProjektId::KEIN_WUNSCH; 5]);
                             }
                             schueler.wishes = schueler.wishes.map(|mut wishes| {
                                 wishes[idx as usize] = projekt_id;
@@ -469,7 +503,7 @@ impl Component for Schueler {
 
                     data.schueler = schueler_map;
 
-                    ctx.link().send_message(Msg::DataSet(data));
+                    self.data.aendern(data, beschreibung);
                 }
 
                 true
@@ -487,6 +521,7 @@ pub struct SchuelerTableLine {
     pub wuensche: [Option<(ProjektId, String)>; 5],
     pub partner: Option<(SchuelerId, String, Klasse, Option<SchuelerId>)>,
     pub partner_raw: Option<String>,
+    pub merkmale: String,
     pub fest: bool,
     pub ignorieren: bool,
 }
@@ -612,6 +647,7 @@ impl TableData for SchuelerTableLine {
             "ignorieren" => Ok(html! {
                 <span><Checkbox value={self.ignorieren} object_key={"ignorieren"} schueler={self.id} /></span>
             }),
+            "merkmale" => Ok(html! (<span>{ self.merkmale.clone() }</span>)),
             _ => Ok(html! {}),
         }
     }
@@ -646,6 +682,7 @@ impl TableData for SchuelerTableLine {
             )),
             "fest" => Ok(serde_value::Value::Bool(self.fest)),
             "ignorieren" => Ok(serde_value::Value::Bool(self.ignorieren)),
+            "merkmale" => Ok(serde_value::Value::String(self.merkmale.clone())),
             _ => Ok(serde_value::to_value(()).unwrap()),
         }
     }
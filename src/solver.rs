@@ -1,16 +1,28 @@
 // pub mod custom_constraints;
+pub mod diagnose;
+pub mod worker;
 
 // use csv::ReaderBuilder;
 use crate::{
     Projekt,
-    types::{ProjektId, SaveFileSchueler, SchuelerId},
+    types::{ProjektId, SaveFileSchueler, SchuelerId, SolveReport, SolverSettings},
 };
+#[cfg(target_arch = "wasm32")]
 use gloo_console::log;
 use good_lp::{
     Expression, ProblemVariables, ResolutionError, Solution, default_solver, solvers::SolverModel,
     variable,
 };
-use std::collections::{BTreeMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+// Außerhalb des Browsers (in Tests) gibt es keine Konsole
+#[cfg(not(target_arch = "wasm32"))]
+macro_rules! log {
+    ($($text:expr),* $(,)?) => {{
+        $(let _ = &$text;)*
+    }};
+}
 
 // #[wasm_bindgen]
 // pub fn solve_test(projects: JsValue, students: JsValue, feste_zuordnung: JsValue) -> JsValue {
@@ -102,26 +114,44 @@ use std::collections::{BTreeMap, HashSet};
 //     }
 // }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SolverResult {
+    pub x: Vec<Vec<f64>>,
+    pub cancelled: BTreeSet<ProjektId>,
+    pub report: SolveReport,
+}
+
 pub fn solve_good_lp(
     projects: &BTreeMap<ProjektId, Projekt>,
     students: &BTreeMap<SchuelerId, SaveFileSchueler>,
     feste_zuordnung: &BTreeMap<SchuelerId, ProjektId>,
-) -> Result<Vec<Vec<f64>>, ResolutionError> {
-    web_sys::console::log_1(&"Creating parameters".into());
-    let weights = [5.0, 4.0, 3.0, 2.0, 1.0];
-    let partner_weight = 2.0;
+    settings: &SolverSettings,
+    fortschritt: &dyn Fn(&str),
+) -> Result<SolverResult, ResolutionError> {
+    let schritt = |text: &str| {
+        log!(text);
+        fortschritt(text);
+    };
+
+    schritt("Parameter vorbereiten");
+    let weights = settings.weights;
+    let partner_weight = settings.partner_weight;
+    let not_wished_penalty = settings.not_wished_penalty;
 
     let student_ids: Vec<SchuelerId> = students.keys().cloned().collect();
     let project_ids: Vec<ProjektId> = projects.keys().cloned().collect();
 
+    // Ignorierte Projekte bleiben im Modell (Indizes), bekommen aber keine Schüler
+    let ignoriert = |pid: &ProjektId| projects.get(pid).is_some_and(|p| p.ignore);
+
     let mut vars = ProblemVariables::new();
     let n = student_ids.len();
     let _m = project_ids.len();
 
-    web_sys::console::log_1(&"Projektids starting from 0".into());
+    log!("Projektids starting from 0");
     // todo!("Projektids starting from 0");
 
-    web_sys::console::log_1(&"Creating decision vars".into());
+    schritt("Entscheidungsvariablen erstellen");
 
     // decision vars x[s][p]
     let mut x = vec![vec![]; n];
@@ -132,7 +162,7 @@ pub fn solve_good_lp(
         }
     }
 
-    web_sys::console::log_1(&"Creating partner pairs".into());
+    schritt("Partnerpaare suchen");
 
     // partner pairs (unique)
     let mut seen = HashSet::new();
@@ -161,7 +191,7 @@ pub fn solve_good_lp(
         }
     }
 
-    web_sys::console::log_1(&"Creating weights".into());
+    schritt("Partnervariablen erstellen");
 
     // w_ij_p and same_ij
     let mut w = Vec::new();
@@ -177,22 +207,35 @@ pub fn solve_good_lp(
         same.push(svar);
     }
 
-    web_sys::console::log_1(&"Creating objective".into());
+    schritt("Zielfunktion erstellen");
 
     // Objective
     let mut obj = Expression::from(0.0);
 
-    web_sys::console::log_1(&"Creating wishes".into());
+    schritt("Wünsche gewichten");
     // wishes
     for (si, &sid) in student_ids.iter().enumerate() {
         let student = &students[&sid];
         if let Some(wishes) = student.wishes {
             for (wi, &project_id) in wishes.iter().enumerate() {
+                if ignoriert(&project_id) {
+                    continue;
+                }
+
                 let pj = project_ids.iter().position(|&x| x == project_id);
                 if let Some(pj) = pj {
                     obj += weights[wi] * x[si][pj];
                 }
             }
+
+            // Strafe für Projekte, die nicht gewünscht wurden
+            if not_wished_penalty != 0.0 {
+                for (pj, project_id) in project_ids.iter().enumerate() {
+                    if !wishes.contains(project_id) && !ignoriert(project_id) {
+                        obj -= not_wished_penalty * x[si][pj];
+                    }
+                }
+            }
         }
     }
     // partner bonus
@@ -200,12 +243,131 @@ pub fn solve_good_lp(
         obj += partner_weight * svar;
     }
 
-    web_sys::console::log_1(&"Building Problem".into());
+    // Weiche Grenzen: Über- und Unterschreitungen werden bestraft statt verboten
+    let mut slack = Vec::new();
+    if settings.soft_constraints {
+        for &pid in &project_ids {
+            let over = vars.add(variable().min(0.0).name(format!("over_{pid}")));
+            let under = vars.add(variable().min(0.0).name(format!("under_{pid}")));
+            obj -= settings.slack_penalty * over;
+            obj -= settings.slack_penalty * under;
+            slack.push((over, under));
+        }
+    }
+
+    // Projekte können abgesagt werden: opened[p] = 0 => keine Schüler im Projekt
+    let mut opened = Vec::new();
+    if settings.cancel_projects {
+        for &pid in &project_ids {
+            opened.push(vars.add(variable().binary().name(format!("open_{pid}"))));
+        }
+    }
+
+    schritt("Quoten vorbereiten");
+
+    // Fest zugeordnete Schüler zählen nicht zu den Quoten, wie bei den Stufen
+    let ist_fest =
+        |sid: &SchuelerId| feste_zuordnung.contains_key(sid) || students[sid].fest == Some(true);
+
+    // (Projekt, Schüler, Höchstzahl) für Stufenquoten und die Höchstzahl je Klasse
+    let mut quoten = Vec::new();
+    for (pj, &pid) in project_ids.iter().enumerate() {
+        let proj = &projects[&pid];
+        if proj.ignore {
+            continue;
+        }
+
+        for quote in &proj.quoten {
+            let schueler = student_ids
+                .iter()
+                .enumerate()
+                .filter(|(_, sid)| {
+                    !ist_fest(sid)
+                        && !students[*sid].ignore
+                        && students[*sid]
+                            .klasse
+                            .stufe()
+                            .is_some_and(|stufe| quote.stufen().contains(&stufe))
+                })
+                .map(|(si, _)| si)
+                .collect::<Vec<usize>>();
+
+            quoten.push((pj, schueler, quote.max));
+        }
+
+        if let Some(max_pro_klasse) = proj.max_pro_klasse {
+            let mut klassen = BTreeMap::<String, Vec<usize>>::new();
+            for (si, sid) in student_ids.iter().enumerate() {
+                if !ist_fest(sid) && !students[sid].ignore {
+                    klassen
+                        .entry(students[sid].klasse.klasse())
+                        .or_default()
+                        .push(si);
+                }
+            }
+
+            quoten.extend(
+                klassen
+                    .into_values()
+                    .map(|schueler| (pj, schueler, max_pro_klasse)),
+            );
+        }
+    }
+
+    quoten.retain(|(_, schueler, max)| schueler.len() > *max as usize);
+
+    // Weiche Grenzen gelten auch für die Quoten
+    let mut quoten_slack = Vec::new();
+    if settings.soft_constraints {
+        for qi in 0..quoten.len() {
+            let over = vars.add(variable().min(0.0).name(format!("quote_{qi}")));
+            obj -= settings.slack_penalty * over;
+            quoten_slack.push(over);
+        }
+    }
+
+    schritt("Anteile vorbereiten");
+
+    // (Projekt, Schüler mit dem Merkmal, Mindestanteil, Höchstanteil) in Prozent der Teilnehmer
+    let mut anteile = Vec::new();
+    for (pj, &pid) in project_ids.iter().enumerate() {
+        let proj = &projects[&pid];
+        if proj.ignore {
+            continue;
+        }
+
+        for anteil in &proj.anteile {
+            let schueler = student_ids
+                .iter()
+                .enumerate()
+                .filter(|(_, sid)| {
+                    !students[*sid].ignore && anteil.trifft_zu(&students[*sid].merkmale)
+                })
+                .map(|(si, _)| si)
+                .collect::<Vec<usize>>();
+
+            anteile.push((pj, schueler, anteil.min, anteil.max));
+        }
+    }
+
+    // Weiche Anteile werden je Schüler Abweichung bestraft statt erzwungen
+    let mut anteile_slack = Vec::new();
+    if settings.soft_shares {
+        for ai in 0..anteile.len() {
+            let over = vars.add(variable().min(0.0).name(format!("anteil_over_{ai}")));
+            let under = vars.add(variable().min(0.0).name(format!("anteil_under_{ai}")));
+            obj -= settings.share_penalty * over;
+            obj -= settings.share_penalty * under;
+            anteile_slack.push((over, under));
+        }
+    }
+
+    schritt("Problem aufbauen");
 
     // Build problem using MicroLp
     let mut pb = vars.maximise(obj).using(default_solver);
 
-    web_sys::console::log_1(&"Constraint: Student limit".into());
+    schritt("Bedingung: ein Projekt pro Schüler");
 
     // each student exactly one
     for (student_projects, (_schueler_id, schueler)) in x.iter().zip(students.iter()) {
@@ -217,35 +379,75 @@ pub fn solve_good_lp(
         }
     }
 
-    web_sys::console::log_1(&"Constraint: Proj. cap".into());
+    schritt("Bedingung: Teilnehmergrenzen");
 
     // project capacity
     for (pj, &pid) in project_ids.iter().enumerate() {
         let proj = &projects[&pid];
         let sum_p = (0..n).map(|si| x[si][pj]).sum::<Expression>();
 
+        if proj.ignore {
+            pb = pb.with(sum_p.eq(0.0));
+            continue;
+        }
+
+        // Angeheftete Schüler und Schüler mit altem "fest"-Flag erhöhen die Höchstzahl
         let feste_schueler = students
             .iter()
-            .filter(|(_s_id, s)| {
-                s.fest
-                    .and_then(|fest| s.wishes.map(|w| fest && w[0] == pid))
-                    .unwrap_or(false)
+            .filter(|&(s_id, s)| match feste_zuordnung.get(s_id) {
+                Some(&projekt_id) => projekt_id == pid,
+                None => s.fest == Some(true) && s.wishes.is_some_and(|w| w[0] == pid),
             })
             .count() as i32;
 
+        let (over, under) = match slack.get(pj) {
+            Some(&(over, under)) => (Expression::from(over), Expression::from(under)),
+            None => (Expression::from(0.0), Expression::from(0.0)),
+        };
+
+        if let Some(&open) = opened.get(pj) {
+            let max = if *proj.teilnehmer.end() != -1 {
+                (*proj.teilnehmer.end() + feste_schueler) as f64
+            } else {
+                n as f64
+            };
+            let min = if *proj.teilnehmer.start() != -1 {
+                *proj.teilnehmer.start() as f64
+            } else {
+                0.0
+            };
+
+            pb = pb
+                .with(sum_p.clone().leq(max * open + over))
+                .with(sum_p.geq(min * open - under));
+            continue;
+        }
+
         if *proj.teilnehmer.end() != -1 {
             pb = pb.with(
                 sum_p
                     .clone()
-                    .leq((*proj.teilnehmer.end() + feste_schueler) as f64),
+                    .leq((*proj.teilnehmer.end() + feste_schueler) as f64 + over),
             )
         }
         if *proj.teilnehmer.start() != -1 {
-            pb = pb.with(sum_p.geq(Expression::from(*proj.teilnehmer.start() as f64)));
+            pb = pb.with(sum_p.geq(*proj.teilnehmer.start() as f64 - under));
         }
     }
 
-    web_sys::console::log_1(&"Constraint: Stufen".into());
+    // Höchstzahl abgesagter Projekte
+    if let (false, Some(max_cancelled)) = (opened.is_empty(), settings.max_cancelled) {
+        let aktive = opened
+            .iter()
+            .zip(project_ids.iter())
+            .filter(|&(_, pid)| !ignoriert(pid))
+            .map(|(&open, _)| open)
+            .collect::<Vec<_>>();
+        let num_opened = aktive.iter().cloned().sum::<Expression>();
+        pb = pb.with(num_opened.geq(aktive.len() as f64 - max_cancelled as f64));
+    }
+
+    schritt("Bedingung: Stufen");
 
     // Schüler dürfen nur in Projekte, die ihrer Stufe entsprechen, außer sie werden fest zugeordnet
     for (si, &sid) in student_ids.iter().enumerate() {
@@ -270,7 +472,37 @@ pub fn solve_good_lp(
         }
     }
 
-    web_sys::console::log_1(&"Linearizing".into());
+    schritt("Bedingung: Quoten");
+
+    for (qi, (pj, schueler, max)) in quoten.iter().enumerate() {
+        let sum_q = schueler.iter().map(|&si| x[si][*pj]).sum::<Expression>();
+        let over = match quoten_slack.get(qi) {
+            Some(&over) => Expression::from(over),
+            None => Expression::from(0.0),
+        };
+
+        pb = pb.with(sum_q.leq(*max as f64 + over));
+    }
+
+    schritt("Bedingung: Anteile");
+
+    for (ai, (pj, schueler, min, max)) in anteile.iter().enumerate() {
+        let sum_p = (0..n).map(|si| x[si][*pj]).sum::<Expression>();
+        let sum_a = schueler.iter().map(|&si| x[si][*pj]).sum::<Expression>();
+        let (over, under) = match anteile_slack.get(ai) {
+            Some(&(over, under)) => (Expression::from(over), Expression::from(under)),
+            None => (Expression::from(0.0), Expression::from(0.0)),
+        };
+
+        if let Some(max) = max {
+            pb = pb.with((sum_a.clone() - sum_p.clone() * (*max as f64 / 100.0)).leq(over));
+        }
+        if let Some(min) = min {
+            pb = pb.with((sum_a + under - sum_p * (*min as f64 / 100.0)).geq(0.0));
+        }
+    }
+
+    schritt("Bedingung: Partner");
 
     // partner linearization
     for (k, &(i, j)) in pairs.iter().enumerate() {
@@ -291,13 +523,14 @@ pub fn solve_good_lp(
         }
     }
 
-    web_sys::console::log_1(&"Feste Zuordnung".into());
+    schritt("Bedingung: feste Zuordnungen");
 
+    // Eine Anheftung hat Vorrang vor dem alten "fest"-Flag
     for (s_id, s) in students {
-        if Some(true) == s.fest {
+        if Some(true) == s.fest && !feste_zuordnung.contains_key(s_id) {
             let projekt_id = s.wishes.and_then(|w| w.first().cloned());
 
-            if let Some(projekt_id) = projekt_id {
+            if let Some(projekt_id) = projekt_id.filter(|p_id| !ignoriert(p_id)) {
                 let s_idx = student_ids.iter().position(|&sid| sid == *s_id).unwrap();
                 let p_idx = project_ids
                     .iter()
@@ -311,103 +544,49 @@ pub fn solve_good_lp(
         }
     }
 
-    web_sys::console::log_1(&"Solving".into());
+    for (s_id, projekt_id) in feste_zuordnung {
+        let s_idx = student_ids.iter().position(|sid| sid == s_id);
+        let p_idx = project_ids.iter().position(|pid| pid == projekt_id);
 
-    let solution = pb.solve()?;
+        if ignoriert(projekt_id) {
+            log!(format!(
+                "{s_id}: {projekt_id} (angeheftet, Projekt ignoriert)"
+            ));
+            continue;
+        }
 
-    web_sys::console::log_1(&"Solved".into());
+        if let (Some(s_idx), Some(p_idx)) = (s_idx, p_idx) {
+            log!(format!("{s_id}: {projekt_id} (angeheftet)"));
 
-    // 1) Build student → project map
-    let mut student_assignment: Vec<(SchuelerId, ProjektId)> = Vec::new();
-    for (s_idx, (&s_uuid, _student)) in students.iter().enumerate() {
-        for (p_idx, &p_id) in project_ids.iter().enumerate() {
-            if solution.value(x[s_idx][p_idx]) > 0.5 {
-                student_assignment.push((s_uuid, p_id));
-                break;
-            }
+            pb = pb.with(Expression::from(x[s_idx][p_idx]).eq(1.0))
         }
     }
 
-    // 2) Build project → count map
-    let mut project_counts: BTreeMap<ProjektId, usize> = BTreeMap::new();
-    for (_ref_student, proj_id) in &student_assignment {
-        *project_counts.entry(*proj_id).or_default() += 1;
-    }
+    schritt("Lösen");
 
-    // 3) Build wish‐rank histogram
-    let mut wish_hist: [usize; 5] = [0; 5];
-    let mut not_wished_projects: usize = 0;
-    for (sid, (&_s_idx, student)) in students.iter().enumerate() {
-        if let Some(wishes) = student.wishes {
-            let mut in_wishes = false;
-
-            for (wish_rank, &wish_pid) in wishes.iter().enumerate() {
-                let p_idx = project_ids.iter().position(|&id| id == wish_pid);
-                if let Some(p_idx) = p_idx {
-                    // Check if the student is assigned to the project
-                    if solution.value(x[sid][p_idx]) > 0.5 {
-                        wish_hist[wish_rank] += 1;
-                        in_wishes = true;
-                        break;
-                    }
-                }
-            }
-            if !in_wishes {
-                not_wished_projects += 1
-            }
-        }
-    }
+    let solution = pb.solve()?;
 
-    // 4) Number of students who got their partner
-    let mut num = 0;
-    for (student_uuid, project_id) in student_assignment.iter() {
-        let student = students.get(student_uuid);
-        if let Some(student) = student {
-            if let Some(partner_uuid) = student.partner {
-                let partner_project = student_assignment.iter().find_map(|(s_uuid, p_id)| {
-                    if s_uuid == &partner_uuid {
-                        Some(p_id)
-                    } else {
-                        None
-                    }
-                });
+    schritt("Gelöst");
 
-                if let Some(partner_project) = partner_project {
-                    if partner_project == project_id {
-                        num += 1;
-                    }
-                }
+    // 1) Build student → project map
+    let mut student_assignment: BTreeMap<SchuelerId, ProjektId> = BTreeMap::new();
+    for (s_idx, &s_uuid) in students.keys().enumerate() {
+        for (p_idx, &p_id) in project_ids.iter().enumerate() {
+            if solution.value(x[s_idx][p_idx]) > 0.5 {
+                student_assignment.insert(s_uuid, p_id);
+                break;
             }
         }
     }
 
-    web_sys::console::log_2(&"Schueler mit Partnern:".to_string().into(), &num.into());
-
-    web_sys::console::log_5(
-        &wish_hist[0].into(),
-        &wish_hist[1].into(),
-        &wish_hist[2].into(),
-        &wish_hist[3].into(),
-        &wish_hist[4].into(),
-    );
-
-    // // ——— Print results ———
+    // 2) Projektbelegung, Wunsch-Histogramm und Partner
+    let report = SolveReport::new(students, &student_assignment);
 
-    // // 1) Students and their assigned project
-    // println!("Student Assignments:");
-    // for (student_name, proj_name) in &student_assignment {
-    //     println!("- {:<20} → {}", student_name, proj_name);
-    // }
-    // println!();
-
-    // 2) Projects and how many students each got
     log!("Project Loads:");
     for pid in &project_ids {
-        let count = project_counts.get(pid).copied().unwrap_or(0);
-
-        let projekt = projects.get(pid);
+        let count = report.project_counts.get(pid).copied().unwrap_or(0);
 
-        if let Some(projekt) = projekt {
+        if let Some(projekt) = projects.get(pid) {
             log!(format!(
                 "- {:<20} : {} Schueler von {}-{}",
                 projekt.name,
@@ -417,19 +596,268 @@ pub fn solve_good_lp(
             ));
         }
     }
-    println!();
 
-    // 3) Wish‐rank histogram
     log!("Wish Satisfaction:");
-    for (i, &count) in wish_hist.iter().enumerate() {
+    for (i, &count) in report.gesamt.wish_hist.iter().enumerate() {
         log!(format!("- {}. wish: {} students", i + 1, count));
     }
-    log!(format!("not-wished: {} schueler", not_wished_projects));
+    log!(format!("not-wished: {} schueler", report.gesamt.not_wished));
+    log!(format!(
+        "Schueler mit Partnern: {}",
+        report.gesamt.partner_erhalten
+    ));
+
+    let cancelled = opened
+        .iter()
+        .zip(project_ids.iter())
+        .filter(|&(&open, pid)| solution.value(open) < 0.5 && !ignoriert(pid))
+        .map(|(_, &pid)| pid)
+        .collect::<BTreeSet<ProjektId>>();
+
+    if !cancelled.is_empty() {
+        log!(format!("abgesagt: {} Projekte", cancelled.len()));
+    }
 
     let x = x
         .iter()
         .map(|y| y.iter().map(|v| solution.value(*v)).collect::<Vec<f64>>())
         .collect::<Vec<_>>();
 
-    Ok(x)
+    Ok(SolverResult {
+        x,
+        cancelled,
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::types::{Klasse, MerkmalAnteil, SaveFileProjekt, StufenQuote};
+
+    fn projekt(name: &str) -> SaveFileProjekt {
+        SaveFileProjekt {
+            name: name.to_string(),
+            min_teilnehmer: 0,
+            max_teilnehmer: 10,
+            min_stufe: 5,
+            max_stufe: 13,
+            ignore: false,
+            num_einteilung: None,
+            abgesagt: false,
+            raum: String::new(),
+            betreuer: String::new(),
+            quoten: Vec::new(),
+            max_pro_klasse: None,
+            anteile: Vec::new(),
+        }
+    }
+
+    // Alle Schüler wünschen sich nur Projekt A
+    fn schueler(klassen: &[&str]) -> BTreeMap<SchuelerId, SaveFileSchueler> {
+        klassen
+            .iter()
+            .enumerate()
+            .map(|(idx, klasse)| {
+                (
+                    SchuelerId::new(Uuid::from_u128(idx as u128 + 1)),
+                    SaveFileSchueler {
+                        uid: idx as u32,
+                        name: format!("Schüler {idx}"),
+                        wishes: Some([
+                            ProjektId::new(0),
+                            ProjektId::KEIN_WUNSCH,
+                            ProjektId::KEIN_WUNSCH,
+                            ProjektId::KEIN_WUNSCH,
+                            ProjektId::KEIN_WUNSCH,
+                        ]),
+                        partner_raw: None,
+                        ignore: false,
+                        fest: Some(false),
+                        klasse: Klasse::new(klasse.to_string()),
+                        partner: None,
+                        merkmale: BTreeMap::new(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn loesen(
+        a: SaveFileProjekt,
+        schueler: &BTreeMap<SchuelerId, SaveFileSchueler>,
+        settings: &SolverSettings,
+    ) -> SolverResult {
+        let projekte = BTreeMap::from([
+            (ProjektId::new(0), Projekt::from(a)),
+            (ProjektId::new(1), Projekt::from(projekt("B"))),
+        ]);
+
+        solve_good_lp(&projekte, schueler, &BTreeMap::new(), settings, &|_| {}).unwrap()
+    }
+
+    // Klassen der Schüler, die in Projekt A eingeteilt wurden
+    fn klassen_in_a(
+        result: &SolverResult,
+        schueler: &BTreeMap<SchuelerId, SaveFileSchueler>,
+    ) -> Vec<String> {
+        result
+            .x
+            .iter()
+            .zip(schueler.values())
+            .filter(|(zeile, _)| zeile[0] >= 0.5)
+            .map(|(_, s)| s.klasse.klasse())
+            .collect()
+    }
+
+    #[test]
+    fn stufenquote_begrenzt_schueler_der_stufe() {
+        let schueler = schueler(&["5a", "5a", "5b", "5b", "6a"]);
+        let a = SaveFileProjekt {
+            quoten: StufenQuote::lesen("5: 2").unwrap(),
+            ..projekt("A")
+        };
+
+        let result = loesen(a, &schueler, &SolverSettings::default());
+        let klassen = klassen_in_a(&result, &schueler);
+
+        assert_eq!(klassen.len(), 3);
+        assert_eq!(klassen.iter().filter(|k| k.starts_with('5')).count(), 2);
+        assert!(klassen.contains(&"6a".to_string()));
+    }
+
+    #[test]
+    fn hoechstzahl_je_klasse() {
+        let schueler = schueler(&["7a", "7a", "7a", "7b", "7b"]);
+        let a = SaveFileProjekt {
+            max_pro_klasse: Some(1),
+            ..projekt("A")
+        };
+
+        let mut klassen =
+            klassen_in_a(&loesen(a, &schueler, &SolverSettings::default()), &schueler);
+        klassen.sort();
+
+        assert_eq!(klassen, vec!["7a".to_string(), "7b".to_string()]);
+    }
+
+    #[test]
+    fn weiche_grenzen_erlauben_mehr_als_die_quote() {
+        let schueler = schueler(&["5a", "5a", "5a"]);
+        let a = SaveFileProjekt {
+            quoten: StufenQuote::lesen("5: 1").unwrap(),
+            ..projekt("A")
+        };
+        let settings = SolverSettings {
+            soft_constraints: true,
+            slack_penalty: 1.0,
+            ..SolverSettings::default()
+        };
+
+        assert_eq!(
+            klassen_in_a(&loesen(a, &schueler, &settings), &schueler).len(),
+            3
+        );
+    }
+
+    fn mit_geschlecht(
+        mut schueler: BTreeMap<SchuelerId, SaveFileSchueler>,
+        geschlechter: &[&str],
+    ) -> BTreeMap<SchuelerId, SaveFileSchueler> {
+        for (s, geschlecht) in schueler.values_mut().zip(geschlechter) {
+            s.merkmale
+                .insert("Geschlecht".to_string(), geschlecht.to_string());
+        }
+        schueler
+    }
+
+    // Geschlechter der Schüler, die in Projekt A eingeteilt wurden
+    fn geschlechter_in_a(
+        result: &SolverResult,
+        schueler: &BTreeMap<SchuelerId, SaveFileSchueler>,
+    ) -> Vec<String> {
+        let mut geschlechter = result
+            .x
+            .iter()
+            .zip(schueler.values())
+            .filter(|(zeile, _)| zeile[0] >= 0.5)
+            .map(|(_, s)| s.merkmale["Geschlecht"].clone())
+            .collect::<Vec<String>>();
+        geschlechter.sort();
+        geschlechter
+    }
+
+    #[test]
+    fn hoechstanteil_wird_eingehalten() {
+        let schueler = mit_geschlecht(schueler(&["5a"; 4]), &["w", "w", "w", "m"]);
+        let a = SaveFileProjekt {
+            anteile: MerkmalAnteil::lesen("Geschlecht=w: -50%").unwrap(),
+            ..projekt("A")
+        };
+
+        let result = loesen(a, &schueler, &SolverSettings::default());
+
+        assert_eq!(geschlechter_in_a(&result, &schueler), vec!["m", "w"]);
+    }
+
+    #[test]
+    fn mindestanteil_wird_eingehalten() {
+        let schueler = mit_geschlecht(schueler(&["5a"; 4]), &["w", "m", "m", "m"]);
+        let a = SaveFileProjekt {
+            anteile: MerkmalAnteil::lesen("Geschlecht=w: 50-%").unwrap(),
+            ..projekt("A")
+        };
+
+        let result = loesen(a, &schueler, &SolverSettings::default());
+
+        assert_eq!(geschlechter_in_a(&result, &schueler), vec!["m", "w"]);
+    }
+
+    #[test]
+    fn weiche_anteile_erlauben_abweichungen() {
+        let schueler = mit_geschlecht(schueler(&["5a"; 4]), &["w", "w", "w", "m"]);
+        let a = SaveFileProjekt {
+            anteile: MerkmalAnteil::lesen("Geschlecht=w: -50%").unwrap(),
+            ..projekt("A")
+        };
+        let settings = SolverSettings {
+            soft_shares: true,
+            share_penalty: 1.0,
+            ..SolverSettings::default()
+        };
+
+        let result = loesen(a, &schueler, &settings);
+
+        assert_eq!(geschlechter_in_a(&result, &schueler).len(), 4);
+    }
+
+    #[test]
+    fn angeheftete_schueler_erhoehen_die_hoechstzahl() {
+        let schueler = schueler(&["5a", "5a", "5b"]);
+        let a = SaveFileProjekt {
+            max_teilnehmer: 1,
+            ..projekt("A")
+        };
+        let projekte = BTreeMap::from([
+            (ProjektId::new(0), Projekt::from(a)),
+            (ProjektId::new(1), Projekt::from(projekt("B"))),
+        ]);
+        let feste_zuordnung = schueler
+            .keys()
+            .map(|&s_id| (s_id, ProjektId::new(0)))
+            .collect::<BTreeMap<SchuelerId, ProjektId>>();
+
+        let result = solve_good_lp(
+            &projekte,
+            &schueler,
+            &feste_zuordnung,
+            &SolverSettings::default(),
+            &|_| {},
+        )
+        .unwrap();
+
+        assert_eq!(klassen_in_a(&result, &schueler).len(), 3);
+    }
 }
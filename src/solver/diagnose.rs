@@ -0,0 +1,458 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Projekt,
+    types::{ProjektId, SaveFileSchueler, SchuelerId, SolverSettings},
+};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Konflikt {
+    MindestteilnehmerSumme {
+        summe: i64,
+        schueler: usize,
+    },
+    KapazitaetSumme {
+        summe: i64,
+        schueler: usize,
+    },
+    ZuWenigeBerechtigte {
+        projekt: String,
+        min_teilnehmer: i32,
+        berechtigt: usize,
+    },
+    StufeUeberKapazitaet {
+        stufe: u32,
+        schueler: usize,
+        kapazitaet: i64,
+        projekte: Vec<String>,
+    },
+    KeinProjektFuerStufe {
+        stufe: u32,
+        schueler: usize,
+    },
+    FestIgnoriert {
+        schueler: String,
+        projekt: String,
+    },
+    QuotenUnterMindestanzahl {
+        projekt: String,
+        plaetze: i64,
+        min_teilnehmer: i32,
+    },
+    AnteilOhneSchueler {
+        projekt: String,
+        anteil: String,
+    },
+    Unbekannt(String),
+}
+
+impl fmt::Display for Konflikt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Konflikt::MindestteilnehmerSumme { summe, schueler } => write!(
+                f,
+                "Die Mindestteilnehmerzahlen aller Projekte ergeben {summe}, es nehmen aber nur {schueler} Schüler teil"
+            ),
+            Konflikt::KapazitaetSumme { summe, schueler } => write!(
+                f,
+                "Alle Projekte zusammen bieten nur {summe} Plätze für {schueler} Schüler"
+            ),
+            Konflikt::ZuWenigeBerechtigte {
+                projekt,
+                min_teilnehmer,
+                berechtigt,
+            } => write!(
+                f,
+                "Projekt \"{projekt}\" braucht mindestens {min_teilnehmer} Teilnehmer, aber nur {berechtigt} Schüler dürfen es belegen"
+            ),
+            Konflikt::StufeUeberKapazitaet {
+                stufe,
+                schueler,
+                kapazitaet,
+                projekte,
+            } => write!(
+                f,
+                "Stufe {stufe} hat {schueler} Schüler, die Projekte für diese Stufe ({}) bieten aber nur {kapazitaet} Plätze",
+                projekte.join(", ")
+            ),
+            Konflikt::KeinProjektFuerStufe { stufe, schueler } => write!(
+                f,
+                "Für Stufe {stufe} ({schueler} Schüler) gibt es kein passendes Projekt"
+            ),
+            Konflikt::FestIgnoriert { schueler, projekt } => write!(
+                f,
+                "{schueler} ist fest \"{projekt}\" zugeordnet, wird aber ignoriert"
+            ),
+            Konflikt::QuotenUnterMindestanzahl {
+                projekt,
+                plaetze,
+                min_teilnehmer,
+            } => write!(
+                f,
+                "Projekt \"{projekt}\" braucht mindestens {min_teilnehmer} Teilnehmer, die Quoten lassen aber nur {plaetze} zu"
+            ),
+            Konflikt::AnteilOhneSchueler { projekt, anteil } => write!(
+                f,
+                "Projekt \"{projekt}\" verlangt einen Mindestanteil ({anteil}), aber kein Schüler hat dieses Merkmal"
+            ),
+            Konflikt::Unbekannt(fehler) => {
+                write!(f, "Keine Ursache gefunden, der Solver meldet: {fehler}")
+            }
+        }
+    }
+}
+
+pub fn diagnose(
+    projects: &BTreeMap<ProjektId, Projekt>,
+    students: &BTreeMap<SchuelerId, SaveFileSchueler>,
+    feste_zuordnung: &BTreeMap<SchuelerId, ProjektId>,
+    settings: &SolverSettings,
+) -> Vec<Konflikt> {
+    let mut konflikte = Vec::new();
+
+    // Ignorierte Projekte nehmen an der Einteilung nicht teil
+    let projects = &projects
+        .iter()
+        .filter(|(_, p)| !p.ignore)
+        .map(|(&p_id, p)| (p_id, p.clone()))
+        .collect::<BTreeMap<ProjektId, Projekt>>();
+
+    let aktive_schueler = students
+        .iter()
+        .filter(|(_, s)| !s.ignore)
+        .collect::<Vec<(&SchuelerId, &SaveFileSchueler)>>();
+
+    // Feste Schüler (alter Weg über den ersten Wunsch) dürfen in jedes Projekt
+    let ist_fest = |s_id: &SchuelerId, s: &SaveFileSchueler| {
+        feste_zuordnung.contains_key(s_id) || s.fest == Some(true)
+    };
+
+    let summe_min = projects
+        .values()
+        .map(|p| p.get_min_teilnehmer() as i64)
+        .sum::<i64>();
+
+    // Weiche Grenzen erlauben Über- und Unterschreitungen, abgesagte Projekte brauchen kein Minimum
+    let harte_grenzen = !settings.soft_constraints;
+    let harte_minima = harte_grenzen && !settings.cancel_projects;
+
+    if harte_minima && summe_min > aktive_schueler.len() as i64 {
+        konflikte.push(Konflikt::MindestteilnehmerSumme {
+            summe: summe_min,
+            schueler: aktive_schueler.len(),
+        });
+    }
+
+    if harte_grenzen && projects.values().all(|p| *p.teilnehmer.end() != -1) {
+        let summe_max = projects
+            .values()
+            .map(|p| p.get_max_teilnehmer() as i64)
+            .sum::<i64>();
+
+        if summe_max < aktive_schueler.len() as i64 {
+            konflikte.push(Konflikt::KapazitaetSumme {
+                summe: summe_max,
+                schueler: aktive_schueler.len(),
+            });
+        }
+    }
+
+    for (p_id, projekt) in projects {
+        let berechtigt = aktive_schueler
+            .iter()
+            .filter(|&&(s_id, s)| {
+                feste_zuordnung.get(s_id) == Some(p_id)
+                    || (!feste_zuordnung.contains_key(s_id)
+                        && (s.fest == Some(true)
+                            || s.klasse
+                                .stufe()
+                                .is_none_or(|stufe| projekt.stufen.contains(&stufe))))
+            })
+            .count();
+
+        if harte_minima && (berechtigt as i64) < projekt.get_min_teilnehmer() as i64 {
+            konflikte.push(Konflikt::ZuWenigeBerechtigte {
+                projekt: projekt.name.clone(),
+                min_teilnehmer: projekt.get_min_teilnehmer(),
+                berechtigt,
+            });
+        }
+
+        // Plätze, die Stufenquoten und die Höchstzahl je Klasse zusammen höchstens zulassen
+        let stufen_abgedeckt = projekt
+            .stufen
+            .clone()
+            .all(|stufe| projekt.quoten.iter().any(|q| q.stufen().contains(&stufe)));
+        let quoten_plaetze = stufen_abgedeckt.then(|| {
+            projekt
+                .quoten
+                .iter()
+                .map(|quote| quote.max as i64)
+                .sum::<i64>()
+        });
+        let klassen_plaetze = projekt.max_pro_klasse.map(|max| {
+            let klassen = aktive_schueler
+                .iter()
+                .filter(|&&(s_id, s)| {
+                    !ist_fest(s_id, s)
+                        && s.klasse
+                            .stufe()
+                            .is_none_or(|stufe| projekt.stufen.contains(&stufe))
+                })
+                .map(|(_, s)| s.klasse.klasse())
+                .collect::<BTreeSet<String>>();
+
+            klassen.len() as i64 * max as i64
+        });
+        let feste_schueler = aktive_schueler
+            .iter()
+            .filter(|&&(s_id, s)| {
+                feste_zuordnung.get(s_id) == Some(p_id)
+                    || (!feste_zuordnung.contains_key(s_id)
+                        && s.fest == Some(true)
+                        && s.wishes.is_some_and(|wishes| wishes[0] == *p_id))
+            })
+            .count() as i64;
+
+        if let Some(plaetze) = quoten_plaetze.into_iter().chain(klassen_plaetze).min() {
+            let plaetze = plaetze + feste_schueler;
+
+            if harte_minima && plaetze < projekt.get_min_teilnehmer() as i64 {
+                konflikte.push(Konflikt::QuotenUnterMindestanzahl {
+                    projekt: projekt.name.clone(),
+                    plaetze,
+                    min_teilnehmer: projekt.get_min_teilnehmer(),
+                });
+            }
+        }
+
+        // Ein Mindestanteil ohne passende Schüler lässt nur ein leeres Projekt zu
+        let harte_anteile = harte_minima && !settings.soft_shares;
+        for anteil in &projekt.anteile {
+            let vorhanden = aktive_schueler
+                .iter()
+                .any(|(_, s)| anteil.trifft_zu(&s.merkmale));
+
+            if harte_anteile
+                && !vorhanden
+                && anteil.min.is_some_and(|min| min > 0)
+                && projekt.get_min_teilnehmer() > 0
+            {
+                konflikte.push(Konflikt::AnteilOhneSchueler {
+                    projekt: projekt.name.clone(),
+                    anteil: anteil.to_string(),
+                });
+            }
+        }
+    }
+
+    let stufen = aktive_schueler
+        .iter()
+        .filter(|&&(s_id, s)| !ist_fest(s_id, s))
+        .filter_map(|(_, s)| s.klasse.stufe())
+        .collect::<BTreeSet<u32>>();
+
+    for stufe in stufen {
+        let schueler = aktive_schueler
+            .iter()
+            .filter(|&&(s_id, s)| !ist_fest(s_id, s) && s.klasse.stufe() == Some(stufe))
+            .count();
+
+        let passende_projekte = projects
+            .values()
+            .filter(|p| p.stufen.contains(&stufe))
+            .collect::<Vec<&Projekt>>();
+
+        if passende_projekte.is_empty() {
+            konflikte.push(Konflikt::KeinProjektFuerStufe { stufe, schueler });
+            continue;
+        }
+
+        if !harte_grenzen || passende_projekte.iter().any(|p| *p.teilnehmer.end() == -1) {
+            continue;
+        }
+
+        let kapazitaet = passende_projekte
+            .iter()
+            .map(|p| p.get_max_teilnehmer() as i64)
+            .sum::<i64>();
+
+        if (schueler as i64) > kapazitaet {
+            konflikte.push(Konflikt::StufeUeberKapazitaet {
+                stufe,
+                schueler,
+                kapazitaet,
+                projekte: passende_projekte.iter().map(|p| p.name.clone()).collect(),
+            });
+        }
+    }
+
+    for (s_id, p_id) in feste_zuordnung {
+        if let Some(schueler) = students.get(s_id).filter(|s| s.ignore) {
+            konflikte.push(Konflikt::FestIgnoriert {
+                schueler: format!("{} ({})", schueler.name, schueler.klasse.klasse()),
+                projekt: projects
+                    .get(p_id)
+                    .map(|p| p.name.clone())
+                    .unwrap_or(p_id.to_string()),
+            });
+        }
+    }
+
+    konflikte
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::types::{Klasse, SaveFileProjekt, StufenQuote};
+
+    fn projekt(name: &str) -> SaveFileProjekt {
+        SaveFileProjekt {
+            name: name.to_string(),
+            min_teilnehmer: 0,
+            max_teilnehmer: 10,
+            min_stufe: 5,
+            max_stufe: 13,
+            ignore: false,
+            num_einteilung: None,
+            abgesagt: false,
+            raum: String::new(),
+            betreuer: String::new(),
+            quoten: Vec::new(),
+            max_pro_klasse: None,
+            anteile: Vec::new(),
+        }
+    }
+
+    fn schueler(klassen: &[&str]) -> BTreeMap<SchuelerId, SaveFileSchueler> {
+        klassen
+            .iter()
+            .enumerate()
+            .map(|(idx, klasse)| {
+                (
+                    SchuelerId::new(Uuid::from_u128(idx as u128 + 1)),
+                    SaveFileSchueler {
+                        uid: idx as u32,
+                        name: format!("Schüler {idx}"),
+                        wishes: Some([ProjektId::KEIN_WUNSCH; 5]),
+                        partner_raw: None,
+                        ignore: false,
+                        fest: Some(false),
+                        klasse: Klasse::new(klasse.to_string()),
+                        partner: None,
+                        merkmale: BTreeMap::new(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    // Projekt A hat die Id 0, Projekt B die Id 1
+    fn pruefen(
+        a: SaveFileProjekt,
+        b: SaveFileProjekt,
+        schueler: &BTreeMap<SchuelerId, SaveFileSchueler>,
+        feste_zuordnung: &BTreeMap<SchuelerId, ProjektId>,
+    ) -> Vec<Konflikt> {
+        let projekte = BTreeMap::from([
+            (ProjektId::new(0), Projekt::from(a)),
+            (ProjektId::new(1), Projekt::from(b)),
+        ]);
+
+        diagnose(
+            &projekte,
+            schueler,
+            feste_zuordnung,
+            &SolverSettings::default(),
+        )
+    }
+
+    #[test]
+    fn summe_der_mindestteilnehmer() {
+        let a = SaveFileProjekt {
+            min_teilnehmer: 3,
+            ..projekt("A")
+        };
+        let b = SaveFileProjekt {
+            min_teilnehmer: 3,
+            ..projekt("B")
+        };
+
+        let konflikte = pruefen(a, b, &schueler(&["5a"; 4]), &BTreeMap::new());
+
+        assert_eq!(
+            konflikte,
+            vec![Konflikt::MindestteilnehmerSumme {
+                summe: 6,
+                schueler: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn stufe_ohne_projekt() {
+        let a = SaveFileProjekt {
+            max_stufe: 10,
+            ..projekt("A")
+        };
+        let b = SaveFileProjekt {
+            max_stufe: 10,
+            ..projekt("B")
+        };
+
+        let konflikte = pruefen(a, b, &schueler(&["5a", "12a"]), &BTreeMap::new());
+
+        assert_eq!(
+            konflikte,
+            vec![Konflikt::KeinProjektFuerStufe {
+                stufe: 12,
+                schueler: 1
+            }]
+        );
+    }
+
+    // Wie im Solver erhöhen angeheftete Schüler die Höchstzahl
+    #[test]
+    fn angeheftete_schueler_ueber_der_hoechstzahl() {
+        let a = SaveFileProjekt {
+            max_teilnehmer: 1,
+            ..projekt("A")
+        };
+        let schueler = schueler(&["5a"; 3]);
+        let feste_zuordnung = schueler
+            .keys()
+            .map(|&s_id| (s_id, ProjektId::new(0)))
+            .collect::<BTreeMap<SchuelerId, ProjektId>>();
+
+        let konflikte = pruefen(a, projekt("B"), &schueler, &feste_zuordnung);
+
+        assert_eq!(konflikte, Vec::new());
+    }
+
+    #[test]
+    fn quote_unter_der_mindestanzahl() {
+        let a = SaveFileProjekt {
+            min_teilnehmer: 5,
+            max_stufe: 6,
+            quoten: StufenQuote::lesen("5-6: 3").unwrap(),
+            ..projekt("A")
+        };
+
+        let konflikte = pruefen(a, projekt("B"), &schueler(&["5a"; 6]), &BTreeMap::new());
+
+        assert_eq!(
+            konflikte,
+            vec![Konflikt::QuotenUnterMindestanzahl {
+                projekt: "A".to_string(),
+                plaetze: 3,
+                min_teilnehmer: 5
+            }]
+        );
+    }
+}
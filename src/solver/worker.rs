@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use gloo::utils::window;
+use serde::{Deserialize, Serialize};
+use web_sys::{
+    Blob, BlobPropertyBag, DedicatedWorkerGlobalScope, ErrorEvent, MessageEvent, Url, Worker,
+    js_sys::{Array, global},
+    wasm_bindgen::{JsCast, JsValue, closure::Closure},
+};
+
+use crate::{
+    Projekt,
+    solver::{
+        SolverResult,
+        diagnose::{Konflikt, diagnose},
+        solve_good_lp,
+    },
+    types::{ProjektId, SaveFileSchueler, SchuelerId, SolverSettings},
+};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SolverAnfrage {
+    pub projekte: BTreeMap<ProjektId, Projekt>,
+    pub schueler: BTreeMap<SchuelerId, SaveFileSchueler>,
+    pub feste_zuordnung: BTreeMap<SchuelerId, ProjektId>,
+    pub settings: SolverSettings,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum SolverAntwort {
+    Fortschritt(String),
+    Ergebnis(Result<SolverResult, Vec<Konflikt>>),
+}
+
+fn loesen(anfrage: SolverAnfrage, antworten: &dyn Fn(SolverAntwort)) {
+    let fortschritt = |schritt: &str| antworten(SolverAntwort::Fortschritt(schritt.to_string()));
+
+    let result = solve_good_lp(
+        &anfrage.projekte,
+        &anfrage.schueler,
+        &anfrage.feste_zuordnung,
+        &anfrage.settings,
+        &fortschritt,
+    );
+
+    let ergebnis = result.map_err(|fehler| {
+        let mut konflikte = diagnose(
+            &anfrage.projekte,
+            &anfrage.schueler,
+            &anfrage.feste_zuordnung,
+            &anfrage.settings,
+        );
+
+        if konflikte.is_empty() {
+            konflikte.push(Konflikt::Unbekannt(fehler.to_string()));
+        }
+
+        konflikte
+    });
+
+    antworten(SolverAntwort::Ergebnis(ergebnis));
+}
+
+// Einstiegspunkt im Worker, Anfragen und Antworten werden als JSON verschickt
+pub fn registrieren() {
+    let scope = global().unchecked_into::<DedicatedWorkerGlobalScope>();
+
+    let antworten = {
+        let scope = scope.clone();
+
+        move |antwort: SolverAntwort| {
+            if let Ok(json) = serde_json::to_string(&antwort) {
+                let _ = scope.post_message(&JsValue::from(json));
+            }
+        }
+    };
+
+    let onmessage = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+        if let Some(anfrage) = event
+            .data()
+            .as_string()
+            .and_then(|json| serde_json::from_str::<SolverAnfrage>(&json).ok())
+        {
+            loesen(anfrage, &antworten);
+        }
+    });
+
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+}
+
+// Jeder Lauf bekommt einen eigenen Worker. Beim Verwerfen wird er beendet, auch mitten im Lösen.
+pub struct SolverVerbindung {
+    worker: Worker,
+    _onmessage: Closure<dyn Fn(MessageEvent)>,
+    _onerror: Closure<dyn Fn(ErrorEvent)>,
+    _onmessageerror: Closure<dyn Fn(MessageEvent)>,
+}
+
+impl SolverVerbindung {
+    pub fn starten(
+        pfad: &str,
+        anfrage: &SolverAnfrage,
+        onantwort: impl Fn(SolverAntwort) + 'static,
+    ) -> Result<Self, JsValue> {
+        let worker = worker_erstellen(pfad)?;
+        let onantwort = Rc::new(onantwort);
+
+        // Ohne Antwort würde die Seite endlos auf den Solver warten, Fehler beenden den Lauf daher
+        let fehler = |onantwort: &Rc<dyn Fn(SolverAntwort)>, text: String| {
+            onantwort(SolverAntwort::Ergebnis(Err(vec![Konflikt::Unbekannt(
+                text,
+            )])))
+        };
+
+        let onmessage = {
+            let onantwort: Rc<dyn Fn(SolverAntwort)> = onantwort.clone();
+
+            Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+                match event
+                    .data()
+                    .as_string()
+                    .and_then(|json| serde_json::from_str::<SolverAntwort>(&json).ok())
+                {
+                    Some(antwort) => onantwort(antwort),
+                    None => fehler(
+                        &onantwort,
+                        "Die Antwort des Solvers konnte nicht gelesen werden".to_string(),
+                    ),
+                }
+            })
+        };
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        // Z.B. ein Panic im Solver, der den Worker abstürzen lässt
+        let onerror = {
+            let onantwort: Rc<dyn Fn(SolverAntwort)> = onantwort.clone();
+
+            Closure::<dyn Fn(ErrorEvent)>::new(move |event: ErrorEvent| {
+                fehler(
+                    &onantwort,
+                    format!("Der Solver ist abgestürzt: {}", event.message()),
+                )
+            })
+        };
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        let onmessageerror = {
+            let onantwort: Rc<dyn Fn(SolverAntwort)> = onantwort;
+
+            Closure::<dyn Fn(MessageEvent)>::new(move |_: MessageEvent| {
+                fehler(
+                    &onantwort,
+                    "Die Antwort des Solvers konnte nicht gelesen werden".to_string(),
+                )
+            })
+        };
+        worker.set_onmessageerror(Some(onmessageerror.as_ref().unchecked_ref()));
+
+        let json = serde_json::to_string(anfrage).map_err(|fehler| fehler.to_string())?;
+        worker.post_message(&JsValue::from(json))?;
+
+        Ok(Self {
+            worker,
+            _onmessage: onmessage,
+            _onerror: onerror,
+            _onmessageerror: onmessageerror,
+        })
+    }
+}
+
+impl Drop for SolverVerbindung {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+// Der von Trunk erzeugte Worker-Code wird über ein kleines Ladeskript gestartet
+fn worker_erstellen(pfad: &str) -> Result<Worker, JsValue> {
+    let js_url = Url::new_with_base(pfad, &window().location().href()?)?.to_string();
+    let wasm_url = js_url.replace(".js", "_bg.wasm");
+
+    let skript = Array::new();
+    skript.push(&JsValue::from(format!(
+        r#"importScripts("{js_url}");wasm_bindgen("{wasm_url}");"#
+    )));
+
+    let optionen = BlobPropertyBag::new();
+    optionen.set_type("application/javascript");
+    let blob = Blob::new_with_str_sequence_and_options(&skript, &optionen)?;
+
+    Worker::new(&Url::create_object_url_with_blob(&blob)?)
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    DomException, Event, IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode,
+    js_sys::{Array, Function, Promise},
+    wasm_bindgen::{JsCast, JsValue, closure::Closure},
+};
+
+use crate::{Data, types::SaveFile};
+
+const DATENBANK: &str = "projekttage";
+const ARBEITSBEREICHE: &str = "arbeitsbereiche";
+
+pub const STANDARD_ARBEITSBEREICH: &str = "Projekttage";
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SpeicherFehler {
+    IndexedDb(String),
+    Speicherstand(String),
+}
+
+impl fmt::Display for SpeicherFehler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpeicherFehler::IndexedDb(fehler) => write!(f, "Browserspeicher: {fehler}"),
+            SpeicherFehler::Speicherstand(fehler) => {
+                write!(
+                    f,
+                    "Gespeicherte Daten können nicht gelesen werden: {fehler}"
+                )
+            }
+        }
+    }
+}
+
+impl From<JsValue> for SpeicherFehler {
+    fn from(wert: JsValue) -> Self {
+        let text = match wert.dyn_ref::<DomException>() {
+            Some(exception) => exception.message(),
+            None => wert.as_string().unwrap_or(format!("{wert:?}")),
+        };
+
+        SpeicherFehler::IndexedDb(text)
+    }
+}
+
+// Wartet auf das Ergebnis einer IndexedDB-Anfrage
+async fn warten(anfrage: &IdbRequest) -> Result<JsValue, SpeicherFehler> {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let erfolg = anfrage.clone();
+        let onsuccess = Closure::once_into_js(move |_: Event| {
+            let ergebnis = erfolg.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::NULL, &ergebnis);
+        });
+
+        let fehler = anfrage.clone();
+        let onerror = Closure::once_into_js(move |_: Event| {
+            let exception = fehler
+                .error()
+                .ok()
+                .flatten()
+                .map(JsValue::from)
+                .unwrap_or(JsValue::UNDEFINED);
+            let _ = reject.call1(&JsValue::NULL, &exception);
+        });
+
+        anfrage.set_onsuccess(Some(onsuccess.unchecked_ref()));
+        anfrage.set_onerror(Some(onerror.unchecked_ref()));
+    });
+
+    Ok(JsFuture::from(promise).await?)
+}
+
+async fn oeffnen() -> Result<IdbDatabase, SpeicherFehler> {
+    let factory = web_sys::window()
+        .and_then(|window| window.indexed_db().ok().flatten())
+        .ok_or(SpeicherFehler::IndexedDb(
+            "IndexedDB wird von diesem Browser nicht unterstützt".to_string(),
+        ))?;
+
+    let anfrage = factory.open_with_u32(DATENBANK, 1)?;
+
+    let upgrade = anfrage.clone();
+    let onupgradeneeded = Closure::once_into_js(move |_: Event| {
+        if let Ok(db) = upgrade.result().and_then(|db| db.dyn_into::<IdbDatabase>())
+            && !db.object_store_names().contains(ARBEITSBEREICHE)
+        {
+            let _ = db.create_object_store(ARBEITSBEREICHE);
+        }
+    });
+    anfrage.set_onupgradeneeded(Some(onupgradeneeded.unchecked_ref()));
+
+    Ok(warten(&anfrage).await?.unchecked_into())
+}
+
+async fn arbeitsbereiche_oeffnen(
+    modus: IdbTransactionMode,
+) -> Result<IdbObjectStore, SpeicherFehler> {
+    let db = oeffnen().await?;
+
+    Ok(db
+        .transaction_with_str_and_mode(ARBEITSBEREICHE, modus)?
+        .object_store(ARBEITSBEREICHE)?)
+}
+
+pub async fn arbeitsbereiche() -> Result<Vec<String>, SpeicherFehler> {
+    let store = arbeitsbereiche_oeffnen(IdbTransactionMode::Readonly).await?;
+    let namen = warten(&store.get_all_keys()?).await?;
+
+    Ok(Array::from(&namen)
+        .iter()
+        .filter_map(|name| name.as_string())
+        .collect())
+}
+
+// Arbeitsbereiche werden wie der heruntergeladene Speicherstand als JSON abgelegt
+pub async fn laden(name: &str) -> Result<Option<Data>, SpeicherFehler> {
+    let store = arbeitsbereiche_oeffnen(IdbTransactionMode::Readonly).await?;
+    let wert = warten(&store.get(&JsValue::from_str(name))?).await?;
+
+    let Some(json) = wert.as_string() else {
+        return Ok(None);
+    };
+
+    SaveFile::lesen(json.as_bytes())
+        .map(|save_file| Some(save_file.into()))
+        .map_err(|fehler| SpeicherFehler::Speicherstand(fehler.to_string()))
+}
+
+pub async fn speichern(name: &str, data: Data) -> Result<(), SpeicherFehler> {
+    let json = serde_json::to_string(&SaveFile::from(data))
+        .map_err(|fehler| SpeicherFehler::Speicherstand(fehler.to_string()))?;
+
+    let store = arbeitsbereiche_oeffnen(IdbTransactionMode::Readwrite).await?;
+    warten(&store.put_with_key(&JsValue::from_str(&json), &JsValue::from_str(name))?).await?;
+
+    Ok(())
+}
+
+pub async fn loeschen(name: &str) -> Result<(), SpeicherFehler> {
+    let store = arbeitsbereiche_oeffnen(IdbTransactionMode::Readwrite).await?;
+    warten(&store.delete(&JsValue::from_str(name))?).await?;
+
+    Ok(())
+}
+
+pub fn aktiver_arbeitsbereich() -> Option<String> {
+    LocalStorage::get("arbeitsbereich").ok()
+}
+
+pub fn aktiven_arbeitsbereich_setzen(name: &str) {
+    let _ = LocalStorage::set("arbeitsbereich", name);
+}
+
+// Lädt den zuletzt benutzten Arbeitsbereich. Beim ersten Start werden die Daten
+// aus dem alten LocalStorage-Speicher in einen Arbeitsbereich übernommen.
+pub async fn starten() -> Result<(String, Data, Vec<String>), SpeicherFehler> {
+    let mut namen = arbeitsbereiche().await?;
+
+    if namen.is_empty() {
+        let data = SaveFile::load_from_local_storage()
+            .map(Data::from)
+            .unwrap_or_default();
+
+        speichern(STANDARD_ARBEITSBEREICH, data).await?;
+        namen.push(STANDARD_ARBEITSBEREICH.to_string());
+    }
+
+    let name = aktiver_arbeitsbereich()
+        .filter(|name| namen.contains(name))
+        .unwrap_or(namen[0].clone());
+
+    let data = laden(&name).await?.unwrap_or_default();
+
+    Ok((name, data, namen))
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct DruckEinstellungen {
+    // Anzahl der Anwesenheitsspalten auf den Teilnehmerlisten
+    pub projekttage: u32,
+    // Text der Benachrichtigungszettel mit Platzhaltern wie {name}
+    pub vorlage: String,
+}
+
+impl Default for DruckEinstellungen {
+    fn default() -> Self {
+        Self {
+            projekttage: 3,
+            vorlage: "Hallo {name} ({klasse}),\ndu bist im Projekt \"{projekt}\" (dein {wunsch}).\nRaum: {raum}\nBetreuer: {betreuer}".to_string(),
+        }
+    }
+}
@@ -14,23 +14,33 @@ impl fmt::Display for ProjektId {
 }
 
 impl ProjektId {
+    // "Kein Wunsch" wird als -1 gespeichert
+    pub const KEIN_WUNSCH: ProjektId = ProjektId(u32::MAX);
+
     pub fn id(&self) -> u32 {
         self.0
     }
-}
 
-impl From<String> for ProjektId {
-    fn from(value: String) -> Self {
-        let id: i32 = str::parse::<i32>(&value).expect("Falsche ProjektId");
-        let id = if id < 0 { u32::MAX } else { id as u32 };
+    pub fn new(id: u32) -> Self {
         Self(id)
     }
 }
 
-impl std::ops::Sub<u32> for ProjektId {
-    type Output = ProjektId;
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FalscheProjektId(pub String);
 
-    fn sub(self, rhs: u32) -> Self::Output {
-        ProjektId(self.0 - rhs)
+impl fmt::Display for FalscheProjektId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" ist keine gültige Projektnummer", self.0)
+    }
+}
+
+impl TryFrom<String> for ProjektId {
+    type Error = FalscheProjektId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let id = str::parse::<i32>(value.trim()).map_err(|_| FalscheProjektId(value.clone()))?;
+        let id = if id < 0 { u32::MAX } else { id as u32 };
+        Ok(Self(id))
     }
 }
@@ -24,7 +24,7 @@ impl Klasse {
 
             let res = regex.find(self.0.as_str());
 
-            res.map(|matching| str::parse(matching.as_str()).unwrap())
+            res.and_then(|matching| str::parse(matching.as_str()).ok())
         }
     }
 }
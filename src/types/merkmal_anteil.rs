@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// Mindest- und Höchstanteil (in Prozent der Teilnehmer) der Schüler mit `merkmal` = `wert`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MerkmalAnteil {
+    pub merkmal: String,
+    pub wert: String,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl MerkmalAnteil {
+    // Liest Anteile wie "Geschlecht=w: 40-60%, Förderbedarf=ja: -20%", leerer Text bedeutet keine Anteile.
+    // Ein einzelner Wert wie "Geschlecht=w: 50%" ist Mindest- und Höchstanteil zugleich.
+    pub fn lesen(text: &str) -> Option<Vec<MerkmalAnteil>> {
+        text.split([',', ';'])
+            .map(str::trim)
+            .filter(|teil| !teil.is_empty())
+            .map(|teil| {
+                let (merkmal, rest) = teil.split_once('=')?;
+                let (wert, anteil) = rest.rsplit_once(':')?;
+                let anteil = anteil.trim().trim_end_matches('%');
+                let (min, max) = anteil.split_once('-').unwrap_or((anteil, anteil));
+
+                let prozent = |text: &str| match text.trim() {
+                    "" => Some(None),
+                    text => text.parse::<u32>().ok().filter(|&p| p <= 100).map(Some),
+                };
+
+                let anteil = MerkmalAnteil {
+                    merkmal: merkmal.trim().to_string(),
+                    wert: wert.trim().to_string(),
+                    min: prozent(min)?,
+                    max: prozent(max)?,
+                };
+
+                let gueltig = !anteil.merkmal.is_empty()
+                    && !anteil.wert.is_empty()
+                    && (anteil.min.is_some() || anteil.max.is_some())
+                    && anteil.min.unwrap_or(0) <= anteil.max.unwrap_or(100);
+
+                gueltig.then_some(anteil)
+            })
+            .collect()
+    }
+
+    pub fn trifft_zu(&self, merkmale: &BTreeMap<String, String>) -> bool {
+        merkmale
+            .get(&self.merkmal)
+            .is_some_and(|wert| wert.trim().to_lowercase() == self.wert.to_lowercase())
+    }
+}
+
+impl fmt::Display for MerkmalAnteil {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => {
+                write!(f, "{}={}: {min}%", self.merkmal, self.wert)
+            }
+            (min, max) => write!(
+                f,
+                "{}={}: {}-{}%",
+                self.merkmal,
+                self.wert,
+                min.map(|min| min.to_string()).unwrap_or_default(),
+                max.map(|max| max.to_string()).unwrap_or_default()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anteil(merkmal: &str, wert: &str, min: Option<u32>, max: Option<u32>) -> MerkmalAnteil {
+        MerkmalAnteil {
+            merkmal: merkmal.to_string(),
+            wert: wert.to_string(),
+            min,
+            max,
+        }
+    }
+
+    #[test]
+    fn liste_wird_gelesen() {
+        assert_eq!(
+            MerkmalAnteil::lesen("Geschlecht=w: 40-60%, Förderbedarf=ja: -20%; Geschlecht=m: 30-"),
+            Some(vec![
+                anteil("Geschlecht", "w", Some(40), Some(60)),
+                anteil("Förderbedarf", "ja", None, Some(20)),
+                anteil("Geschlecht", "m", Some(30), None),
+            ])
+        );
+    }
+
+    #[test]
+    fn einzelner_wert_ist_mindest_und_hoechstanteil() {
+        assert_eq!(
+            MerkmalAnteil::lesen("Geschlecht=w: 50%"),
+            Some(vec![anteil("Geschlecht", "w", Some(50), Some(50))])
+        );
+    }
+
+    #[test]
+    fn leerer_text_bedeutet_keine_anteile() {
+        assert_eq!(MerkmalAnteil::lesen(""), Some(Vec::new()));
+        assert_eq!(MerkmalAnteil::lesen(" ; "), Some(Vec::new()));
+    }
+
+    #[test]
+    fn umgekehrter_bereich_wird_abgelehnt() {
+        assert_eq!(MerkmalAnteil::lesen("Geschlecht=w: 60-40%"), None);
+    }
+
+    #[test]
+    fn mehr_als_100_prozent_werden_abgelehnt() {
+        assert_eq!(MerkmalAnteil::lesen("Geschlecht=w: 40-120%"), None);
+        assert_eq!(MerkmalAnteil::lesen("Geschlecht=w: 150%"), None);
+    }
+
+    #[test]
+    fn fehlerhafte_eintraege_werden_abgelehnt() {
+        assert_eq!(MerkmalAnteil::lesen("Geschlecht: 40-60%"), None);
+        assert_eq!(MerkmalAnteil::lesen("Geschlecht=w: -%"), None);
+        assert_eq!(MerkmalAnteil::lesen("=w: 40-60%"), None);
+    }
+
+    #[test]
+    fn anzeige_laesst_sich_wieder_lesen() {
+        let anteile = vec![
+            anteil("Geschlecht", "w", Some(40), Some(60)),
+            anteil("Förderbedarf", "ja", None, Some(20)),
+            anteil("Geschlecht", "m", Some(50), Some(50)),
+        ];
+        let text = anteile
+            .iter()
+            .map(MerkmalAnteil::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        assert_eq!(MerkmalAnteil::lesen(&text), Some(anteile));
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use serde_json::Value;
+
+// Bei jeder Änderung am Format des Speicherstands wird die Version erhöht
+// und eine Migration von der vorherigen Version angehängt.
+pub const AKTUELLE_VERSION: u32 = 2;
+
+// MIGRATIONEN[n] überführt Version n in Version n + 1
+const MIGRATIONEN: [fn(&mut Value); AKTUELLE_VERSION as usize] =
+    [teilnehmer_umbenennen, kein_wunsch_vereinheitlichen];
+
+#[derive(Debug)]
+pub enum SpeicherstandFehler {
+    Json(serde_json::Error),
+    ZuNeu(u32),
+}
+
+impl fmt::Display for SpeicherstandFehler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpeicherstandFehler::Json(err) => write!(f, "{err}"),
+            SpeicherstandFehler::ZuNeu(version) => write!(
+                f,
+                "Der Speicherstand hat Version {version}, diese Version des Programms kann nur Speicherstände bis Version {AKTUELLE_VERSION} lesen"
+            ),
+        }
+    }
+}
+
+impl From<serde_json::Error> for SpeicherstandFehler {
+    fn from(err: serde_json::Error) -> Self {
+        SpeicherstandFehler::Json(err)
+    }
+}
+
+// Speicherstände ohne Versionsangabe haben Version 0
+pub fn migrieren(mut wert: Value) -> Result<Value, SpeicherstandFehler> {
+    let version = wert
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or_default() as u32;
+
+    if version > AKTUELLE_VERSION {
+        return Err(SpeicherstandFehler::ZuNeu(version));
+    }
+
+    for migration in &MIGRATIONEN[version as usize..] {
+        migration(&mut wert);
+    }
+
+    if let Some(objekt) = wert.as_object_mut() {
+        objekt.insert("version".to_string(), AKTUELLE_VERSION.into());
+    }
+
+    Ok(wert)
+}
+
+// Version 0 → 1: Die Teilnehmergrenzen hießen früher "min" und "max"
+fn teilnehmer_umbenennen(wert: &mut Value) {
+    let Some(projekte) = wert.get_mut("projekte").and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    for projekt in projekte.values_mut().filter_map(Value::as_object_mut) {
+        for (alt, neu) in [("min", "min_teilnehmer"), ("max", "max_teilnehmer")] {
+            if let Some(grenze) = projekt.remove(alt) {
+                projekt.entry(neu).or_insert(grenze);
+            }
+        }
+    }
+}
+
+// Version 1 → 2: Ältere Speicherstände haben "Kein Wunsch" als u32::MAX - 1 gespeichert
+fn kein_wunsch_vereinheitlichen(wert: &mut Value) {
+    let Some(schueler) = wert.get_mut("schueler").and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    let wuensche = schueler
+        .values_mut()
+        .filter_map(|s| s.get_mut("wishes"))
+        .filter_map(Value::as_array_mut)
+        .flatten();
+
+    for wunsch in wuensche {
+        if wunsch.as_u64() == Some(u32::MAX as u64 - 1) {
+            *wunsch = u32::MAX.into();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn version_0_wird_auf_aktuelle_version_gebracht() {
+        let alt = json!({
+            "projekte": {
+                "0": { "name": "Theater", "min": 5, "max": 20 }
+            },
+            "schueler": {
+                "a": { "name": "Anna", "wishes": [0, 4294967294u32, 4294967294u32, 4294967294u32, 4294967294u32] },
+                "b": { "name": "Ben", "wishes": null }
+            }
+        });
+
+        let neu = migrieren(alt).unwrap();
+
+        assert_eq!(neu["version"], json!(AKTUELLE_VERSION));
+        assert_eq!(
+            neu["projekte"]["0"],
+            json!({ "name": "Theater", "min_teilnehmer": 5, "max_teilnehmer": 20 })
+        );
+        assert_eq!(
+            neu["schueler"]["a"]["wishes"],
+            json!([0, u32::MAX, u32::MAX, u32::MAX, u32::MAX])
+        );
+        assert_eq!(neu["schueler"]["b"]["wishes"], Value::Null);
+    }
+
+    #[test]
+    fn aktuelle_version_bleibt_unveraendert() {
+        let aktuell = json!({
+            "version": AKTUELLE_VERSION,
+            "projekte": { "0": { "name": "Theater", "min_teilnehmer": 5 } }
+        });
+
+        assert_eq!(migrieren(aktuell.clone()).unwrap(), aktuell);
+    }
+
+    #[test]
+    fn neuere_version_wird_abgelehnt() {
+        let neuer = json!({ "version": AKTUELLE_VERSION + 1 });
+
+        assert!(matches!(
+            migrieren(neuer),
+            Err(SpeicherstandFehler::ZuNeu(version)) if version == AKTUELLE_VERSION + 1
+        ));
+    }
+}
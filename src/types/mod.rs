@@ -1,17 +1,32 @@
 mod klasse;
 
+mod druck_einstellungen;
+
 mod id;
 
+mod merkmal_anteil;
+mod migration;
+
 mod save_file;
 
+mod snapshot;
+mod solve_report;
+mod solver_settings;
+mod stufen_quote;
+
 pub mod schueler_file;
 pub mod schueler_liste_file;
 
+pub use druck_einstellungen::DruckEinstellungen;
+
 pub use klasse::Klasse;
 
-pub use id::projekt_id::ProjektId;
+pub use id::projekt_id::{FalscheProjektId, ProjektId};
 pub use id::schueler_id::SchuelerId;
 
+pub use merkmal_anteil::MerkmalAnteil;
+pub use migration::{AKTUELLE_VERSION, SpeicherstandFehler};
+
 pub use save_file::SaveFile;
 
 pub use save_file::SaveFileKlasse;
@@ -19,3 +34,8 @@ pub use save_file::SaveFileProjekt;
 pub use save_file::SaveFileSchueler;
 pub use save_file::SaveFileStufe;
 pub use save_file::SaveFileZuordnung;
+
+pub use snapshot::Snapshot;
+pub use solve_report::{SolveReport, SolveReportZeile};
+pub use solver_settings::SolverSettings;
+pub use stufen_quote::StufenQuote;
@@ -1,12 +1,17 @@
 use std::collections::BTreeMap;
 
 use gloo_console::log;
-use gloo_storage::{LocalStorage, Storage, errors::StorageError};
+use gloo_storage::{LocalStorage, Storage};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::{
     Data, Projekt,
-    types::{Klasse, ProjektId, SchuelerId},
+    types::{
+        AKTUELLE_VERSION, DruckEinstellungen, Klasse, MerkmalAnteil, ProjektId, SchuelerId,
+        Snapshot, SolveReport, SolverSettings, SpeicherstandFehler, StufenQuote,
+        migration::migrieren,
+    },
 };
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
@@ -27,14 +32,24 @@ pub struct SaveFileKlasse {
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct SaveFileProjekt {
     pub name: String,
-    #[serde(alias = "min")]
     pub min_teilnehmer: i32,
-    #[serde(alias = "max")]
     pub max_teilnehmer: i32,
     pub min_stufe: u32,
     pub max_stufe: u32,
     pub ignore: bool,
     pub num_einteilung: Option<u32>,
+    #[serde(default)]
+    pub abgesagt: bool,
+    #[serde(default)]
+    pub raum: String,
+    #[serde(default)]
+    pub betreuer: String,
+    #[serde(default)]
+    pub quoten: Vec<StufenQuote>,
+    #[serde(default)]
+    pub max_pro_klasse: Option<u32>,
+    #[serde(default)]
+    pub anteile: Vec<MerkmalAnteil>,
 }
 
 impl From<SaveFileProjekt> for Projekt {
@@ -44,6 +59,11 @@ impl From<SaveFileProjekt> for Projekt {
             stufen: (val.min_stufe..=val.max_stufe),
             teilnehmer: (val.min_teilnehmer..=val.max_teilnehmer),
             num_einteilung: val.num_einteilung,
+            abgesagt: val.abgesagt,
+            ignore: val.ignore,
+            quoten: val.quoten,
+            max_pro_klasse: val.max_pro_klasse,
+            anteile: val.anteile,
         }
     }
 }
@@ -58,6 +78,9 @@ pub struct SaveFileSchueler {
     pub fest: Option<bool>,
     pub klasse: Klasse,
     pub partner: Option<SchuelerId>,
+    // Frei benannte Merkmale aus der Schülerliste, z.B. "Geschlecht" => "w"
+    #[serde(default)]
+    pub merkmale: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -65,17 +88,38 @@ pub struct SaveFileZuordnung {
     pub id: u32,
     pub schueler: SchuelerId,
     pub projekt: Option<ProjektId>,
+    #[serde(default)]
+    pub gesperrt: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct SaveFile {
+    #[serde(default)]
+    pub version: u32,
     pub klassen: BTreeMap<SaveFileStufe, SaveFileKlasse>,
     pub projekte: BTreeMap<ProjektId, SaveFileProjekt>,
     pub schueler: BTreeMap<SchuelerId, SaveFileSchueler>,
     pub zuordnung: Vec<SaveFileZuordnung>,
+    #[serde(default)]
+    pub feste_zuordnung: BTreeMap<SchuelerId, ProjektId>,
+    #[serde(default)]
+    pub solver_settings: SolverSettings,
+    #[serde(default)]
+    pub report: Option<SolveReport>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub druck: DruckEinstellungen,
 }
 
 impl SaveFile {
+    // Ältere Speicherstände werden vor dem Einlesen auf die aktuelle Version gebracht
+    pub fn lesen(json: &[u8]) -> Result<Self, SpeicherstandFehler> {
+        let wert = migrieren(serde_json::from_slice(json)?)?;
+
+        Ok(serde_json::from_value(wert)?)
+    }
+
     pub fn log(&self) {
         log!(self.klassen.len());
         log!(self.projekte.len());
@@ -83,24 +127,27 @@ impl SaveFile {
         log!(self.zuordnung.len());
     }
 
-    pub fn save_to_local_storage(&self) -> Result<(), StorageError> {
-        LocalStorage::set("klassen", self.klassen.clone())?;
-        LocalStorage::set("projekte", self.projekte.clone())?;
-        LocalStorage::set("schueler", self.schueler.clone())?;
-        LocalStorage::set("zuordnung", self.zuordnung.clone())?;
-
-        // todo!("Use use_state instead");
-
-        Ok(())
-    }
+    pub fn load_from_local_storage() -> Result<Self, SpeicherstandFehler> {
+        let mut wert = Map::new();
+
+        for (schluessel, standard) in [
+            ("version", Value::from(0)),
+            ("klassen", Value::Object(Map::new())),
+            ("projekte", Value::Object(Map::new())),
+            ("schueler", Value::Object(Map::new())),
+            ("zuordnung", Value::Array(Vec::new())),
+            ("feste_zuordnung", Value::Object(Map::new())),
+            ("solver_settings", Value::Null),
+            ("report", Value::Null),
+        ] {
+            let gespeichert = LocalStorage::get::<Value>(schluessel).unwrap_or(standard);
+
+            if !gespeichert.is_null() {
+                wert.insert(schluessel.to_string(), gespeichert);
+            }
+        }
 
-    pub fn load_from_local_storage() -> Result<Self, StorageError> {
-        Ok(Self {
-            klassen: LocalStorage::get("klassen").unwrap_or(BTreeMap::new()),
-            projekte: LocalStorage::get("projekte").unwrap_or(BTreeMap::new()),
-            schueler: LocalStorage::get("schueler").unwrap_or(BTreeMap::new()),
-            zuordnung: LocalStorage::get("zuordnung").unwrap_or(Vec::new()),
-        })
+        Ok(serde_json::from_value(migrieren(Value::Object(wert))?)?)
     }
 }
 
@@ -111,6 +158,28 @@ impl From<SaveFile> for Data {
             schueler: val.schueler,
             zuordnung: val.zuordnung,
             klassen: val.klassen,
+            feste_zuordnung: val.feste_zuordnung,
+            solver_settings: val.solver_settings,
+            report: val.report,
+            snapshots: val.snapshots,
+            druck: val.druck,
+        }
+    }
+}
+
+impl From<Data> for SaveFile {
+    fn from(val: Data) -> Self {
+        SaveFile {
+            version: AKTUELLE_VERSION,
+            klassen: val.klassen,
+            projekte: val.projekte,
+            schueler: val.schueler,
+            zuordnung: val.zuordnung,
+            feste_zuordnung: val.feste_zuordnung,
+            solver_settings: val.solver_settings,
+            report: val.report,
+            snapshots: val.snapshots,
+            druck: val.druck,
         }
     }
 }
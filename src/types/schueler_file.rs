@@ -1,9 +1,13 @@
 use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
-use crate::types::{Klasse, ProjektId, SaveFileSchueler, SchuelerId};
+use crate::{
+    import::{AbgelehnteZeile, schueler::merkmale_aus_feldern},
+    types::{Klasse, ProjektId, SaveFileSchueler, SchuelerId},
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Schueler {
@@ -17,51 +21,94 @@ pub struct Schueler {
     q04_viertwunsch: Option<String>,
     q05_fnftwunsch: Option<String>,
     q06_wunschpartner: Option<String>,
+    // Weitere Spalten wie Geschlecht oder Förderbedarf werden als Merkmale übernommen
+    #[serde(flatten)]
+    weitere_felder: BTreeMap<String, Value>,
 }
 
 impl Schueler {
-    fn get_wuensche(&self) -> Option<[ProjektId; 5]> {
+    fn get_wuensche(&self) -> Result<Option<[ProjektId; 5]>, String> {
         if let (Some(wunsch1), Some(wunsch2), Some(wunsch3), Some(wunsch4), Some(wunsch5)) = (
-            self.q01_erstwunsch.clone(),
-            self.q02_zweitwunsch.clone(),
-            self.q03_drittwunsch.clone(),
-            self.q04_viertwunsch.clone(),
-            self.q05_fnftwunsch.clone(),
+            &self.q01_erstwunsch,
+            &self.q02_zweitwunsch,
+            &self.q03_drittwunsch,
+            &self.q04_viertwunsch,
+            &self.q05_fnftwunsch,
         ) {
-            Some([wunsch1, wunsch2, wunsch3, wunsch4, wunsch5].map(|wunsch| {
-                std::convert::Into::<ProjektId>::into(
-                    (*wunsch.split(" : ").collect::<Vec<&str>>().first().unwrap()).to_string(),
-                ) - 1
-            }))
+            let mut wuensche = [ProjektId::KEIN_WUNSCH; 5];
+
+            for (wunsch, text) in wuensche
+                .iter_mut()
+                .zip([wunsch1, wunsch2, wunsch3, wunsch4, wunsch5])
+            {
+                // Moodle exportiert die Wahl als "Nummer : Name", die Nummern beginnen bei 1
+                let nummer = text.split(" : ").next().unwrap_or_default().to_string();
+                let projekt_id =
+                    ProjektId::try_from(nummer).map_err(|fehler| fehler.to_string())?;
+
+                if projekt_id.id() == 0 || projekt_id == ProjektId::KEIN_WUNSCH {
+                    return Err(format!("\"{text}\" ist keine gültige Projektnummer"));
+                }
+
+                *wunsch = ProjektId::new(projekt_id.id() - 1);
+            }
+
+            Ok(Some(wuensche))
         } else {
-            None
+            Ok(None)
         }
     }
+
+    fn eintrag(&self) -> Result<(SchuelerId, SaveFileSchueler), String> {
+        let uid = str::parse(self.antworten.trim())
+            .map_err(|_| format!("Falsche Schüler-UID \"{}\"", self.antworten))?;
+
+        Ok((
+            SchuelerId::new(self.anmeldename),
+            SaveFileSchueler {
+                name: self.vollstndigername.clone(),
+                uid,
+                wishes: self.get_wuensche()?,
+                partner_raw: self.q06_wunschpartner.clone(),
+                ignore: false,
+                klasse: Klasse::new(self.gruppe.clone()),
+                partner: None,
+                fest: Some(false),
+                merkmale: merkmale_aus_feldern(&self.weitere_felder),
+            },
+        ))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct SchuelerFile([Vec<Schueler>; 1]);
 
-impl From<SchuelerFile> for BTreeMap<SchuelerId, SaveFileSchueler> {
-    fn from(val: SchuelerFile) -> Self {
+// Alle fehlerhaften Einträge werden gesammelt, damit sie in der Datei korrigiert werden können
+impl TryFrom<SchuelerFile> for BTreeMap<SchuelerId, SaveFileSchueler> {
+    type Error = Vec<AbgelehnteZeile>;
+
+    fn try_from(val: SchuelerFile) -> Result<Self, Self::Error> {
+        let [eintraege] = val.0;
+
         let mut schueler_liste = BTreeMap::new();
+        let mut abgelehnt = Vec::new();
 
-        for schueler in val.0.first().unwrap() {
-            schueler_liste.insert(
-                SchuelerId::new(schueler.anmeldename),
-                SaveFileSchueler {
-                    name: schueler.vollstndigername.clone(),
-                    uid: str::parse(&schueler.antworten).expect("Falsche Schueler-UID"),
-                    wishes: schueler.get_wuensche(),
-                    partner_raw: schueler.q06_wunschpartner.clone(),
-                    ignore: false,
-                    klasse: Klasse::new(schueler.gruppe.clone()),
-                    partner: None,
-                    fest: Some(false),
-                },
-            );
+        for (idx, schueler) in eintraege.iter().enumerate() {
+            match schueler.eintrag() {
+                Ok((schueler_id, schueler)) => {
+                    schueler_liste.insert(schueler_id, schueler);
+                }
+                Err(grund) => abgelehnt.push(AbgelehnteZeile {
+                    zeile: idx + 1,
+                    grund: format!("{}: {grund}", schueler.vollstndigername),
+                }),
+            }
         }
 
-        schueler_liste
+        if abgelehnt.is_empty() {
+            Ok(schueler_liste)
+        } else {
+            Err(abgelehnt)
+        }
     }
 }
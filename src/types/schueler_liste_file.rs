@@ -1,10 +1,13 @@
 use std::collections::BTreeMap;
 
-use gloo_console::log;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
-use crate::types::{Klasse, SaveFileSchueler, SchuelerId};
+use crate::{
+    import::{AbgelehnteZeile, schueler::merkmale_aus_feldern},
+    types::{Klasse, SaveFileSchueler, SchuelerId},
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Schueler {
@@ -13,35 +16,72 @@ pub struct Schueler {
     vorname: String,
     nachname: String,
     gruppen: String,
+    // Weitere Spalten wie Geschlecht oder Förderbedarf werden als Merkmale übernommen
+    #[serde(flatten)]
+    weitere_felder: BTreeMap<String, Value>,
+}
+
+impl Schueler {
+    fn name(&self) -> String {
+        format!("{} {}", self.vorname, self.nachname)
+    }
+
+    fn eintrag(&self) -> Result<(SchuelerId, SaveFileSchueler), String> {
+        let uuid = Uuid::parse_str(self.anmeldename.trim())
+            .map_err(|_| format!("Falscher Anmeldename \"{}\"", self.anmeldename))?;
+        let uid = str::parse(self.id.trim())
+            .map_err(|_| format!("Falsche Schüler-UID \"{}\"", self.id))?;
+
+        Ok((
+            SchuelerId::new(uuid),
+            SaveFileSchueler {
+                name: self.name(),
+                uid,
+                wishes: None,
+                partner_raw: None,
+                ignore: true,
+                klasse: Klasse::new(self.gruppen.clone()),
+                partner: None,
+                fest: None,
+                merkmale: merkmale_aus_feldern(&self.weitere_felder),
+            },
+        ))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct SchuelerListeFile([Vec<Schueler>; 1]);
 
-impl From<SchuelerListeFile> for BTreeMap<SchuelerId, SaveFileSchueler> {
-    fn from(val: SchuelerListeFile) -> Self {
-        log!("AAAAAAAA");
+impl TryFrom<SchuelerListeFile> for BTreeMap<SchuelerId, SaveFileSchueler> {
+    type Error = Vec<AbgelehnteZeile>;
+
+    fn try_from(val: SchuelerListeFile) -> Result<Self, Self::Error> {
+        let [eintraege] = val.0;
+
         let mut schueler_liste = BTreeMap::new();
+        let mut abgelehnt = Vec::new();
 
-        for schueler in val.0.first().unwrap() {
+        for (idx, schueler) in eintraege.iter().enumerate() {
+            // Konten ohne Anmeldename (z.B. Lehrkräfte) werden übersprungen
             if schueler.anmeldename.is_empty() {
                 continue;
             }
-            schueler_liste.insert(
-                SchuelerId::new(Uuid::parse_str(&schueler.anmeldename).expect("Falsche Uuid")),
-                SaveFileSchueler {
-                    name: format!("{} {}", schueler.vorname.clone(), schueler.nachname.clone()),
-                    uid: str::parse(&schueler.id).expect("Falsche Schueler-UID"),
-                    wishes: None,
-                    partner_raw: None,
-                    ignore: true,
-                    klasse: Klasse::new(schueler.gruppen.clone()),
-                    partner: None,
-                    fest: None,
-                },
-            );
+
+            match schueler.eintrag() {
+                Ok((schueler_id, schueler)) => {
+                    schueler_liste.insert(schueler_id, schueler);
+                }
+                Err(grund) => abgelehnt.push(AbgelehnteZeile {
+                    zeile: idx + 1,
+                    grund: format!("{}: {grund}", schueler.name()),
+                }),
+            }
         }
 
-        schueler_liste
+        if abgelehnt.is_empty() {
+            Ok(schueler_liste)
+        } else {
+            Err(abgelehnt)
+        }
     }
 }
@@ -0,0 +1,80 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use web_sys::js_sys::Date;
+
+use crate::{
+    Data,
+    types::{ProjektId, SaveFileZuordnung, SolveReport, SolverSettings},
+};
+
+// Gespeicherte Einteilung mit den Einstellungen und der Statistik, mit denen sie entstanden ist
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    pub name: String,
+    pub zeitpunkt: String,
+    pub zuordnung: Vec<SaveFileZuordnung>,
+    #[serde(default)]
+    pub abgesagt: BTreeSet<ProjektId>,
+    pub solver_settings: SolverSettings,
+    pub report: Option<SolveReport>,
+    // Nummer des Solver-Laufs bei automatisch angelegten Snapshots
+    #[serde(default)]
+    pub lauf: Option<u32>,
+}
+
+impl Snapshot {
+    pub fn new(name: String, data: &Data) -> Self {
+        let jetzt = Date::new_0();
+
+        Self {
+            name,
+            zeitpunkt: format!(
+                "{:02}.{:02}.{} {:02}:{:02}",
+                jetzt.get_date(),
+                jetzt.get_month() + 1,
+                jetzt.get_full_year(),
+                jetzt.get_hours(),
+                jetzt.get_minutes()
+            ),
+            zuordnung: data.zuordnung.clone(),
+            abgesagt: data
+                .projekte
+                .iter()
+                .filter(|(_, projekt)| projekt.abgesagt)
+                .map(|(&p_id, _)| p_id)
+                .collect(),
+            solver_settings: data.solver_settings.clone(),
+            report: data.report.clone(),
+            lauf: None,
+        }
+    }
+
+    pub fn wiederherstellen(&self, data: &mut Data) {
+        data.zuordnung = self.zuordnung.clone();
+        data.solver_settings = self.solver_settings.clone();
+        data.report = self.report.clone();
+
+        for (projekt_id, projekt) in data.projekte.iter_mut() {
+            projekt.abgesagt = self.abgesagt.contains(projekt_id);
+        }
+
+        // Schüler und Projekte können seit dem Snapshot entfernt worden sein
+        data.bereinigen();
+        data.teilnehmer_zaehlen();
+    }
+
+    // Anzahl der Schüler, die in beiden Einteilungen in verschiedenen Projekten sind
+    pub fn unterschiede(&self, andere: &Snapshot) -> usize {
+        self.zuordnung
+            .iter()
+            .filter(|zuordnung| {
+                andere
+                    .zuordnung
+                    .iter()
+                    .find(|z| z.schueler == zuordnung.schueler)
+                    .is_some_and(|z| z.projekt != zuordnung.projekt)
+            })
+            .count()
+    }
+}
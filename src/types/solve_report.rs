@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ProjektId, SaveFileSchueler, SchuelerId};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct SolveReportZeile {
+    pub schueler: usize,
+    pub wish_hist: [usize; 5],
+    pub not_wished: usize,
+    pub ohne_wuensche: usize,
+    pub partner_gewuenscht: usize,
+    pub partner_erhalten: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct SolveReport {
+    pub gesamt: SolveReportZeile,
+    pub stufen: BTreeMap<u32, SolveReportZeile>,
+    pub project_counts: BTreeMap<ProjektId, usize>,
+}
+
+impl SolveReport {
+    pub fn new(
+        students: &BTreeMap<SchuelerId, SaveFileSchueler>,
+        student_assignment: &BTreeMap<SchuelerId, ProjektId>,
+    ) -> Self {
+        let mut report = SolveReport::default();
+
+        for proj_id in student_assignment.values() {
+            *report.project_counts.entry(*proj_id).or_default() += 1;
+        }
+
+        for (s_id, student) in students {
+            let Some(projekt_id) = student_assignment.get(s_id) else {
+                continue;
+            };
+
+            let mut zeilen = vec![&mut report.gesamt];
+            if let Some(stufe) = student.klasse.stufe() {
+                zeilen.push(report.stufen.entry(stufe).or_default());
+            }
+
+            let wish_rank = student
+                .wishes
+                .and_then(|wishes| wishes.iter().position(|w| w == projekt_id));
+
+            let partner = student
+                .partner
+                .filter(|partner_id| students.contains_key(partner_id));
+            let partner_erhalten = partner
+                .and_then(|partner_id| student_assignment.get(&partner_id))
+                .is_some_and(|partner_projekt| partner_projekt == projekt_id);
+
+            for zeile in zeilen {
+                zeile.schueler += 1;
+
+                match (student.wishes, wish_rank) {
+                    (None, _) => zeile.ohne_wuensche += 1,
+                    (Some(_), Some(wish_rank)) => zeile.wish_hist[wish_rank] += 1,
+                    (Some(_), None) => zeile.not_wished += 1,
+                }
+
+                if partner.is_some() {
+                    zeile.partner_gewuenscht += 1;
+                }
+                if partner_erhalten {
+                    zeile.partner_erhalten += 1;
+                }
+            }
+        }
+
+        report
+    }
+}
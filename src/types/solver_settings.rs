@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct SolverSettings {
+    pub weights: [f64; 5],
+    pub partner_weight: f64,
+    pub not_wished_penalty: f64,
+    pub keep_manual: bool,
+    pub soft_constraints: bool,
+    pub slack_penalty: f64,
+    pub cancel_projects: bool,
+    pub max_cancelled: Option<u32>,
+    pub soft_shares: bool,
+    pub share_penalty: f64,
+}
+
+impl Default for SolverSettings {
+    fn default() -> Self {
+        Self {
+            weights: [5.0, 4.0, 3.0, 2.0, 1.0],
+            partner_weight: 2.0,
+            not_wished_penalty: 0.0,
+            keep_manual: true,
+            soft_constraints: false,
+            slack_penalty: 10.0,
+            cancel_projects: false,
+            max_cancelled: None,
+            soft_shares: false,
+            share_penalty: 5.0,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+// Höchstens `max` Schüler aus den Stufen `min_stufe` bis `max_stufe` in einem Projekt
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StufenQuote {
+    pub min_stufe: u32,
+    pub max_stufe: u32,
+    pub max: u32,
+}
+
+impl StufenQuote {
+    pub fn stufen(&self) -> RangeInclusive<u32> {
+        self.min_stufe..=self.max_stufe
+    }
+
+    // Liest Quoten wie "5-6: 8, 7-8: 8" oder "5: 4", leerer Text bedeutet keine Quoten
+    pub fn lesen(text: &str) -> Option<Vec<StufenQuote>> {
+        text.split([',', ';'])
+            .map(str::trim)
+            .filter(|teil| !teil.is_empty())
+            .map(|teil| {
+                let (stufen, max) = teil.split_once(':')?;
+                let (min_stufe, max_stufe) = match stufen.split_once('-') {
+                    Some((von, bis)) => (von.trim().parse().ok()?, bis.trim().parse().ok()?),
+                    None => {
+                        let stufe = stufen.trim().parse().ok()?;
+                        (stufe, stufe)
+                    }
+                };
+
+                (min_stufe <= max_stufe).then_some(StufenQuote {
+                    min_stufe,
+                    max_stufe,
+                    max: max.trim().parse().ok()?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for StufenQuote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.min_stufe == self.max_stufe {
+            write!(f, "{}: {}", self.min_stufe, self.max)
+        } else {
+            write!(f, "{}-{}: {}", self.min_stufe, self.max_stufe, self.max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(min_stufe: u32, max_stufe: u32, max: u32) -> StufenQuote {
+        StufenQuote {
+            min_stufe,
+            max_stufe,
+            max,
+        }
+    }
+
+    #[test]
+    fn liste_wird_gelesen() {
+        assert_eq!(
+            StufenQuote::lesen("5-6: 8, 7-8: 8; 9: 4"),
+            Some(vec![quote(5, 6, 8), quote(7, 8, 8), quote(9, 9, 4)])
+        );
+    }
+
+    #[test]
+    fn leerer_text_bedeutet_keine_quoten() {
+        assert_eq!(StufenQuote::lesen(""), Some(Vec::new()));
+        assert_eq!(StufenQuote::lesen(" , "), Some(Vec::new()));
+    }
+
+    #[test]
+    fn umgekehrte_stufen_werden_abgelehnt() {
+        assert_eq!(StufenQuote::lesen("8-5: 4"), None);
+    }
+
+    #[test]
+    fn fehlerhafte_eintraege_werden_abgelehnt() {
+        assert_eq!(StufenQuote::lesen("5-6: 8, 7-8"), None);
+        assert_eq!(StufenQuote::lesen("5-6: viele"), None);
+        assert_eq!(StufenQuote::lesen("a-b: 3"), None);
+    }
+
+    #[test]
+    fn anzeige_laesst_sich_wieder_lesen() {
+        let quoten = vec![quote(5, 6, 8), quote(9, 9, 4)];
+        let text = quoten
+            .iter()
+            .map(StufenQuote::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        assert_eq!(StufenQuote::lesen(&text), Some(quoten));
+    }
+}
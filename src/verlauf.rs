@@ -0,0 +1,144 @@
+use std::{ops::Deref, rc::Rc};
+
+use yew::{Reducible, UseReducerHandle};
+
+use crate::Data;
+
+// Jeder Eintrag hält einen vollständigen Stand der Daten, daher ist die Länge begrenzt
+const MAX_EINTRAEGE: usize = 50;
+
+#[derive(Clone, PartialEq)]
+pub struct VerlaufEintrag {
+    pub beschreibung: String,
+    data: Rc<Data>,
+}
+
+#[derive(Clone, Default)]
+pub struct Verlauf {
+    data: Rc<Data>,
+    // Zustand vor der jeweiligen Änderung, die letzte Änderung steht am Ende
+    rueckgaengig: Vec<VerlaufEintrag>,
+    wiederholen: Vec<VerlaufEintrag>,
+}
+
+pub enum VerlaufAktion {
+    Aendern(Data, String),
+    // Ersetzt die Daten und leert den Verlauf, z.B. beim Wechsel des Arbeitsbereichs
+    Laden(Data),
+    Rueckgaengig(usize),
+    Wiederholen(usize),
+}
+
+impl Reducible for Verlauf {
+    type Action = VerlaufAktion;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut verlauf = (*self).clone();
+
+        match action {
+            VerlaufAktion::Aendern(data, beschreibung) => {
+                if data == *verlauf.data {
+                    return self;
+                }
+
+                let alt = std::mem::replace(&mut verlauf.data, Rc::new(data));
+                verlauf.rueckgaengig.push(VerlaufEintrag {
+                    beschreibung,
+                    data: alt,
+                });
+                if verlauf.rueckgaengig.len() > MAX_EINTRAEGE {
+                    verlauf.rueckgaengig.remove(0);
+                }
+                verlauf.wiederholen.clear();
+            }
+            VerlaufAktion::Laden(data) => {
+                verlauf = Verlauf {
+                    data: Rc::new(data),
+                    ..Verlauf::default()
+                };
+            }
+            VerlaufAktion::Rueckgaengig(schritte) => {
+                for _ in 0..schritte {
+                    let Some(eintrag) = verlauf.rueckgaengig.pop() else {
+                        break;
+                    };
+
+                    let alt = std::mem::replace(&mut verlauf.data, eintrag.data);
+                    verlauf.wiederholen.push(VerlaufEintrag {
+                        beschreibung: eintrag.beschreibung,
+                        data: alt,
+                    });
+                }
+            }
+            VerlaufAktion::Wiederholen(schritte) => {
+                for _ in 0..schritte {
+                    let Some(eintrag) = verlauf.wiederholen.pop() else {
+                        break;
+                    };
+
+                    let alt = std::mem::replace(&mut verlauf.data, eintrag.data);
+                    verlauf.rueckgaengig.push(VerlaufEintrag {
+                        beschreibung: eintrag.beschreibung,
+                        data: alt,
+                    });
+                }
+            }
+        }
+
+        Rc::new(verlauf)
+    }
+}
+
+#[derive(Clone)]
+pub struct DataContext(UseReducerHandle<Verlauf>);
+
+impl DataContext {
+    pub fn new(verlauf: UseReducerHandle<Verlauf>) -> Self {
+        Self(verlauf)
+    }
+
+    pub fn aendern(&self, data: Data, beschreibung: impl Into<String>) {
+        self.0
+            .dispatch(VerlaufAktion::Aendern(data, beschreibung.into()));
+    }
+
+    pub fn laden(&self, data: Data) {
+        self.0.dispatch(VerlaufAktion::Laden(data));
+    }
+
+    pub fn rueckgaengig(&self, schritte: usize) {
+        self.0.dispatch(VerlaufAktion::Rueckgaengig(schritte));
+    }
+
+    pub fn wiederholen(&self, schritte: usize) {
+        self.0.dispatch(VerlaufAktion::Wiederholen(schritte));
+    }
+
+    // Neueste Änderung zuerst
+    pub fn rueckgaengig_liste(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &VerlaufEintrag> + ExactSizeIterator {
+        self.0.rueckgaengig.iter().rev()
+    }
+
+    pub fn wiederholen_liste(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &VerlaufEintrag> + ExactSizeIterator {
+        self.0.wiederholen.iter().rev()
+    }
+}
+
+impl Deref for DataContext {
+    type Target = Data;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.data
+    }
+}
+
+// Jede Änderung erzeugt einen neuen Verlauf, der Vergleich der Zeiger genügt
+impl PartialEq for DataContext {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(&*self.0, &*other.0)
+    }
+}